@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::process::Command as AsyncCommand;
 
-use crate::github::get_access_token;
+use crate::github::{get_access_token, github_api_base, github_host, send_github_request};
+use crate::gitlab::{get_gitlab_token, GITLAB_HOST};
 
 const WORKSPACES_DIR: &str = ".hatch/workspaces";
+const WORKSPACES_DIR_ENV: &str = "HATCH_WORKSPACES_DIR";
+const WORKTREES_DIR_ENV: &str = "HATCH_WORKTREES_DIR";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
@@ -15,6 +18,8 @@ pub struct Repository {
     pub local_path: String,
     pub default_branch: String,
     pub is_private: bool,
+    #[serde(default)]
+    pub is_shallow: bool, // Truncated history; log, blame and merge-base may be incomplete
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,56 +27,220 @@ pub struct GitStatus {
     pub branch: String,
     pub ahead: u32,
     pub behind: u32,
+    /// What `ahead`/`behind` are counted against, e.g. "upstream/main"
+    pub upstream: Option<String>,
     pub staged: Vec<String>,
     pub modified: Vec<String>,
     pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+    pub in_progress_operation: Option<String>, // "merge", "rebase", "cherry-pick", "revert"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloneProgress {
-    pub stage: String, // "counting", "compressing", "receiving", "resolving", "done"
+    pub stage: String, // "counting", "compressing", "receiving", "resolving", "submodules", "done"
     pub percent: u32,
 }
 
-/// Get the base workspaces directory
+/// Get the base workspaces directory. An invalid `HATCH_WORKSPACES_DIR` is an
+/// error rather than a silent fallback, so clones never land somewhere unexpected.
 pub fn get_workspaces_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = std::env::var(WORKSPACES_DIR_ENV).ok().filter(|dir| !dir.trim().is_empty()) {
+        let dir = PathBuf::from(dir.trim());
+        validate_workspaces_override(&dir)?;
+        return Ok(dir);
+    }
+
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
     Ok(home.join(WORKSPACES_DIR))
 }
 
+/// Last workspaces override that passed `validate_workspaces_override`
+static VALIDATED_WORKSPACES_DIR: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+
+/// An override must be absolute and writable. The write probe runs once per
+/// override, not on every lookup.
+pub(crate) fn validate_workspaces_override(dir: &Path) -> Result<(), String> {
+    let mut validated = VALIDATED_WORKSPACES_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if validated.as_deref() == Some(dir) {
+        return Ok(());
+    }
+
+    let unusable = |reason: String| format!("{} is not usable ({}): {}", WORKSPACES_DIR_ENV, dir.display(), reason);
+    if !dir.is_absolute() {
+        return Err(unusable("must be an absolute path".to_string()));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| unusable(e.to_string()))?;
+
+    let probe = dir.join(".hatch-write-test");
+    let written = std::fs::write(&probe, b"");
+    let _ = std::fs::remove_file(&probe);
+    written.map_err(|e| unusable(e.to_string()))?;
+
+    *validated = Some(dir.to_path_buf());
+    Ok(())
+}
+
+/// Directory holding a repo's workspace worktrees. Defaults to `<repo>/worktrees`;
+/// set `HATCH_WORKTREES_DIR` to an absolute path to keep them outside the repo.
+pub fn get_worktrees_dir(repo_path: &Path) -> PathBuf {
+    let external = std::env::var(WORKTREES_DIR_ENV)
+        .ok()
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute());
+
+    match external {
+        Some(dir) => external_worktrees_dir(&dir, get_workspaces_dir().ok().as_deref(), repo_path),
+        None => repo_path.join("worktrees"),
+    }
+}
+
+/// A repo's worktrees directory under `HATCH_WORKTREES_DIR`. Repos cloned into
+/// the workspaces dir keep their `<owner>/<repo>` layout; any other repo gets
+/// `<repo_name>-<hash of its path>`, so same-named repos never share a directory.
+pub(crate) fn external_worktrees_dir(dir: &Path, workspaces_dir: Option<&Path>, repo_path: &Path) -> PathBuf {
+    let repo_path = std::fs::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+    let namespaced = workspaces_dir
+        .and_then(|workspaces| std::fs::canonicalize(workspaces).ok())
+        .and_then(|workspaces| repo_path.strip_prefix(workspaces).ok().map(Path::to_path_buf))
+        .filter(|relative| relative.components().next().is_some());
+    if let Some(relative) = namespaced {
+        return dir.join(relative);
+    }
+
+    match repo_path.file_name() {
+        Some(repo_name) => dir.join(format!(
+            "{}-{:016x}",
+            repo_name.to_string_lossy(),
+            stable_path_hash(&repo_path)
+        )),
+        None => repo_path.join("worktrees"),
+    }
+}
+
+/// FNV-1a over the path's bytes. Unlike `DefaultHasher`, the result never
+/// changes between Rust releases, so worktree directories stay put.
+fn stable_path_hash(path: &Path) -> u64 {
+    path.to_string_lossy()
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+}
+
 /// Clone a repository from GitHub
 #[tauri::command]
-pub async fn git_clone_repo(repo_url: String, repo_name: String) -> Result<Repository, String> {
+pub async fn git_clone_repo(
+    app: tauri::AppHandle,
+    repo_url: String,
+    repo_name: String,
+    ssh_command: Option<String>,
+    depth: Option<u32>,
+    single_branch: Option<bool>,
+    with_submodules: Option<bool>,
+) -> Result<Repository, String> {
+    clone_repo(Some(&app), repo_url, repo_name, ssh_command, depth, single_branch, with_submodules.unwrap_or(false)).await
+}
+
+/// `git_clone_repo`. With `with_submodules`, submodules are initialized after
+/// the clone, and a failure there removes the clone so it can be retried.
+pub(crate) async fn clone_repo(
+    app: Option<&tauri::AppHandle>,
+    repo_url: String,
+    repo_name: String,
+    ssh_command: Option<String>,
+    depth: Option<u32>,
+    single_branch: Option<bool>,
+    with_submodules: bool,
+) -> Result<Repository, String> {
     let workspaces_dir = get_workspaces_dir()?;
 
     // Create workspaces directory if it doesn't exist
     std::fs::create_dir_all(&workspaces_dir)
         .map_err(|e| format!("Failed to create workspaces directory: {}", e))?;
 
-    let local_path = workspaces_dir.join(&repo_name);
+    // Same-named repos from different owners must not collide
+    let local_path = match clone_namespace(&repo_url) {
+        Some(namespace) => namespace.iter().fold(workspaces_dir, |dir, part| dir.join(part)).join(&repo_name),
+        None => workspaces_dir.join(&repo_name),
+    };
 
     // Check if already cloned
     if local_path.exists() {
         return Err(format!("Repository '{}' already exists at {:?}", repo_name, local_path));
     }
 
-    // Clone the repository (gh credential helper handles auth automatically)
-    let output = AsyncCommand::new("git")
-        .args(["clone", &repo_url, local_path.to_str().unwrap()])
+    // Clone the repository. SSH URLs are left to the system SSH agent; over HTTPS,
+    // GitHub auth goes through the gh credential helper and GitLab gets the stored
+    // token as an auth header.
+    let is_ssh = is_ssh_url(&repo_url);
+
+    let depth = depth.filter(|depth| *depth > 0);
+    let mut command = AsyncCommand::new("git");
+    command.arg("clone");
+    if let Some(depth) = depth {
+        command.args(["--depth", &depth.to_string()]);
+    }
+    match single_branch {
+        Some(true) => {
+            command.arg("--single-branch");
+        }
+        // --depth implies --single-branch unless told otherwise
+        Some(false) if depth.is_some() => {
+            command.arg("--no-single-branch");
+        }
+        _ => {}
+    }
+    command
+        .args([&repo_url, local_path.to_str().unwrap()])
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if is_ssh {
+        command.env("GIT_SSH_COMMAND", batch_ssh_command(ssh_command.as_deref()));
+    } else {
+        GitProvider::detect(&repo_url).apply_clone_auth(&mut command);
+    }
+
+    if let Some(parent) = local_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create workspaces directory: {}", e))?;
+    }
+
+    let output = command
         .output()
         .await
         .map_err(|e| format!("Failed to run git clone: {}", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("Host key verification failed") {
+            return Err(format!(
+                "Git clone failed: the SSH host key for {} is not trusted yet. \
+                 Connect once with `ssh -T` from a terminal to verify and add it to known_hosts.",
+                ssh_host(&repo_url).unwrap_or("the remote host")
+            ));
+        }
+        if is_ssh && stderr.contains("Permission denied (publickey") {
+            return Err(format!(
+                "Git clone failed: no SSH key was accepted. Make sure your key is loaded in ssh-agent \
+                 (`ssh-add -l`) or pass a custom SSH command.\n{}",
+                stderr
+            ));
+        }
         return Err(format!("Git clone failed: {}", stderr));
     }
 
+    if with_submodules {
+        let local = local_path.to_string_lossy().to_string();
+        if let Err(error) = update_submodules(app, &local, ssh_command.as_deref()).await {
+            let _ = std::fs::remove_dir_all(&local_path);
+            return Err(format!("Git clone failed: {}", error));
+        }
+    }
+
     // Get default branch
     let default_branch = get_default_branch(&local_path).await?;
 
-    // Parse repo info from URL
-    let full_name = parse_repo_full_name(&repo_url)?;
+    // Parse repo info from URL; repos hosted elsewhere have no GitHub name
+    let full_name = parse_repo_full_name(&repo_url).unwrap_or_else(|_| repo_name.clone());
 
     Ok(Repository {
         id: uuid::Uuid::new_v4().to_string(),
@@ -81,7 +250,123 @@ pub async fn git_clone_repo(repo_url: String, repo_name: String) -> Result<Repos
         local_path: local_path.to_string_lossy().to_string(),
         default_branch,
         is_private: false, // We'll determine this from GitHub API if needed
+        is_shallow: depth.is_some(),
+    })
+}
+
+/// Owner path of a clone URL (`owner`, or `group/subgroup` on GitLab), used to
+/// nest clones under the workspaces directory. `None` for local paths.
+pub(crate) fn clone_namespace(url: &str) -> Option<Vec<String>> {
+    let url = url.trim();
+    let path = match url.split_once("://") {
+        Some((scheme, rest)) if scheme != "file" => rest.split_once('/')?.1,
+        Some(_) => return None,
+        // scp-like `git@host:owner/repo`
+        None => match url.split_once(':') {
+            Some((host, rest)) if !host.contains('/') => rest,
+            _ => return None,
+        },
+    };
+
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let mut parts: Vec<String> = path.split('/').filter(|part| !part.is_empty()).map(str::to_string).collect();
+    parts.pop()?;
+    if parts.is_empty() || parts.iter().any(|part| part == "." || part == ".." || part.contains('\\')) {
+        return None;
+    }
+    Some(parts)
+}
+
+/// The SSH command for clones and fetches. Never block on an interactive
+/// prompt; the agent still supplies keys.
+fn batch_ssh_command(custom: Option<&str>) -> String {
+    custom
+        .filter(|cmd| !cmd.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| std::env::var("GIT_SSH_COMMAND").ok())
+        .unwrap_or_else(|| "ssh -o BatchMode=yes".to_string())
+}
+
+/// Send `username:token` as basic auth on HTTPS requests under `url_prefix`.
+/// The header goes through `GIT_CONFIG_*` env vars, which child git processes
+/// inherit, so the token never shows up in argv, a remote URL or a config file.
+pub(crate) fn with_token_auth<'a>(
+    command: &'a mut AsyncCommand,
+    url_prefix: &str,
+    username: &str,
+    token: &str,
+) -> &'a mut AsyncCommand {
+    use base64::Engine;
+
+    let credentials = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, token));
+    command
+        .env("GIT_CONFIG_COUNT", "1")
+        .env("GIT_CONFIG_KEY_0", format!("http.{}.extraHeader", url_prefix))
+        .env("GIT_CONFIG_VALUE_0", format!("Authorization: Basic {}", credentials))
+}
+
+/// Initialize and update a repository's submodules, recursively. Progress is
+/// reported through `clone-progress` events.
+#[tauri::command]
+pub async fn git_update_submodules(app: tauri::AppHandle, repo_path: String) -> Result<(), String> {
+    update_submodules(Some(&app), &repo_path, None).await
+}
+
+pub(crate) async fn update_submodules(
+    app: Option<&tauri::AppHandle>,
+    repo_path: &str,
+    ssh_command: Option<&str>,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let mut command = AsyncCommand::new("git");
+    command.args(["-C", repo_path]);
+    // Submodules on github.com get the stored token for this command only
+    let token = get_access_token().await;
+    if let Some(token) = &token {
+        with_token_auth(&mut command, "https://github.com/", "x-access-token", token);
+    }
+    command
+        .args(["submodule", "update", "--init", "--recursive", "--progress"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_SSH_COMMAND", batch_ssh_command(ssh_command))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped());
+
+    let mut child = command.spawn().map_err(|e| format!("Failed to update submodules: {}", e))?;
+    let stderr = child.stderr.take().ok_or("Failed to update submodules: stderr not captured")?;
+    let collected = read_git_progress(stderr, |_, percent| {
+        if let Some(app) = app {
+            let _ = app.emit("clone-progress", CloneProgress {
+                stage: "submodules".to_string(),
+                percent: percent.into(),
+            });
+        }
     })
+    .await
+    .map_err(|e| format!("Failed to read submodule output: {}", e))?;
+
+    let status = child.wait().await.map_err(|e| format!("Failed to update submodules: {}", e))?;
+    if !status.success() {
+        let mut stderr = String::from_utf8_lossy(&collected).to_string();
+        if let Some(token) = &token {
+            stderr = stderr.replace(token.as_str(), "***");
+        }
+        let message: Vec<&str> = stderr
+            .lines()
+            .filter_map(|line| line.rsplit('\r').next())
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        return Err(format!("Failed to update submodules: {}", message.join("\n")));
+    }
+
+    if let Some(app) = app {
+        let _ = app.emit("clone-progress", CloneProgress {
+            stage: "done".to_string(),
+            percent: 100,
+        });
+    }
+    Ok(())
 }
 
 /// Open an existing local repository
@@ -128,6 +413,8 @@ pub async fn git_open_local_repo(path: String) -> Result<Repository, String> {
         name.clone()
     };
 
+    let is_shallow = is_shallow_repository(&path).await;
+
     Ok(Repository {
         id: uuid::Uuid::new_v4().to_string(),
         name,
@@ -136,6 +423,7 @@ pub async fn git_open_local_repo(path: String) -> Result<Repository, String> {
         local_path: path,
         default_branch,
         is_private: false,
+        is_shallow,
     })
 }
 
@@ -148,30 +436,62 @@ pub struct WorkspaceResult {
 
 /// Create a new workspace with its own worktree for isolation
 #[tauri::command]
-pub async fn git_create_workspace_branch(repo_path: String, workspace_id: String) -> Result<WorkspaceResult, String> {
+pub async fn git_create_workspace_branch(
+    repo_path: String,
+    workspace_id: String,
+    base_ref: Option<String>,
+) -> Result<WorkspaceResult, String> {
     let branch_name = format!("workspace/{}", workspace_id);
     let repo_path_buf = PathBuf::from(&repo_path);
+    let base_ref = base_ref.filter(|base| !base.trim().is_empty());
 
-    // Create worktrees directory inside the repo's parent workspace folder
-    // Structure: ~/.hatch/workspaces/{repo_name}/worktrees/{workspace_id}
-    let worktrees_dir = repo_path_buf.join("worktrees");
+    // Structure: ~/.hatch/workspaces/{owner}/{repo_name}/worktrees/{workspace_id}, or
+    // {HATCH_WORKTREES_DIR}/{owner}/{repo_name}/{workspace_id} when configured
+    let worktrees_dir = get_worktrees_dir(&repo_path_buf);
     let worktree_path = worktrees_dir.join(&workspace_id);
 
-    std::fs::create_dir_all(&worktrees_dir)
-        .map_err(|e| format!("Failed to create worktrees directory: {}", e))?;
-
     // Fetch latest from origin first
     let _ = AsyncCommand::new("git")
         .args(["-C", &repo_path, "fetch", "origin"])
         .output()
         .await;
 
-    // Get the default branch
-    let default_branch = get_default_branch(&repo_path_buf).await?;
+    // Branch off the requested ref, or the default branch's remote tip
+    let start_point = match base_ref {
+        Some(base_ref) => base_ref,
+        None => {
+            let default_branch = get_default_branch(&repo_path_buf).await?;
+            // Single-branch or shallow clones may not track the default branch yet
+            ensure_remote_branch(&repo_path, &default_branch).await;
+            format!("origin/{}", default_branch)
+        }
+    };
+
+    // Validate before touching anything so a bad ref can't leave a half-made worktree
+    let verify_output = AsyncCommand::new("git")
+        .args([
+            "-C",
+            &repo_path,
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "--end-of-options",
+            &format!("{}^{{commit}}", start_point),
+        ])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to verify base ref: {}", e))?;
+
+    if !verify_output.status.success() {
+        return Err(format!("Base ref '{}' does not exist", start_point));
+    }
+
+    std::fs::create_dir_all(&worktrees_dir)
+        .map_err(|e| format!("Failed to create worktrees directory: {}", e))?;
 
     // Create a new branch for the workspace
     let branch_output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "branch", &branch_name, &format!("origin/{}", default_branch)])
+        .args(["-C", &repo_path, "branch", &branch_name, &start_point])
         .output()
         .await
         .map_err(|e| format!("Failed to create branch: {}", e))?;
@@ -227,205 +547,1265 @@ pub async fn git_status(repo_path: String) -> Result<GitStatus, String> {
         .map_err(|e| format!("Failed to get status: {}", e))?;
 
     let status_str = String::from_utf8_lossy(&status_output.stdout);
+    let parsed = parse_status_porcelain(&status_str);
+    let in_progress_operation = detect_in_progress_operation(&repo_path).await;
 
-    let mut staged = Vec::new();
-    let mut modified = Vec::new();
-    let mut untracked = Vec::new();
+    // Get ahead/behind counts
+    let upstream = resolve_upstream(&repo_path, &branch).await;
+    let (ahead, behind) = match &upstream {
+        Some(upstream) => get_ahead_behind(&repo_path, &branch, upstream).await.unwrap_or((0, 0)),
+        None => (0, 0),
+    };
 
-    for line in status_str.lines() {
+    Ok(GitStatus {
+        branch,
+        ahead,
+        behind,
+        upstream,
+        staged: parsed.staged,
+        modified: parsed.modified,
+        untracked: parsed.untracked,
+        conflicted: parsed.conflicted,
+        in_progress_operation,
+    })
+}
+
+/// Paths from `status --porcelain`, bucketed the way `GitStatus` reports them
+#[derive(Debug, Default)]
+pub(crate) struct PorcelainStatus {
+    pub staged: Vec<String>,
+    pub modified: Vec<String>,
+    pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+}
+
+/// Bucket `status --porcelain` output. Renames report the new path, a file with
+/// both staged and unstaged changes (e.g. `MM`) is listed in both `staged` and
+/// `modified`, and unmerged entries only appear in `conflicted`.
+pub(crate) fn parse_status_porcelain(output: &str) -> PorcelainStatus {
+    let mut status = PorcelainStatus::default();
+
+    for line in output.lines() {
         if line.len() < 3 {
             continue;
         }
         let index_status = line.chars().next().unwrap_or(' ');
         let worktree_status = line.chars().nth(1).unwrap_or(' ');
-        let file = line[3..].to_string();
+        let (file, _) = split_porcelain_rename(&line[3..]);
 
         match (index_status, worktree_status) {
-            ('?', '?') => untracked.push(file),
-            (' ', 'M') | (' ', 'D') => modified.push(file),
-            ('M', _) | ('A', _) | ('D', _) | ('R', _) => staged.push(file),
+            ('?', '?') => {
+                status.untracked.push(file);
+                continue;
+            }
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => {
+                status.conflicted.push(file);
+                continue;
+            }
             _ => {}
         }
+        if matches!(index_status, 'M' | 'A' | 'D' | 'R' | 'C') {
+            status.staged.push(file.clone());
+        }
+        if matches!(worktree_status, 'M' | 'D') {
+            status.modified.push(file);
+        }
     }
 
-    // Get ahead/behind counts
-    let (ahead, behind) = get_ahead_behind(&repo_path, &branch).await.unwrap_or((0, 0));
+    status
+}
 
-    Ok(GitStatus {
-        branch,
-        ahead,
-        behind,
-        staged,
-        modified,
-        untracked,
-    })
+/// Report a merge, rebase, cherry-pick or revert left in progress, based on the
+/// marker files git keeps in the (possibly per-worktree) git directory
+async fn detect_in_progress_operation(repo_path: &str) -> Option<String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--absolute-git-dir"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+
+    let markers = [
+        ("MERGE_HEAD", "merge"),
+        ("REBASE_HEAD", "rebase"),
+        ("rebase-merge", "rebase"),
+        ("rebase-apply", "rebase"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+    ];
+    markers
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, operation)| operation.to_string())
 }
 
-/// Commit all changes with the given message
+/// Abort whichever merge, rebase, cherry-pick or revert is in progress
 #[tauri::command]
-pub async fn git_commit(repo_path: String, message: String) -> Result<String, String> {
-    // Stage all changes
-    let add_output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "add", "-A"])
+pub async fn git_abort(repo_path: String) -> Result<GitStatus, String> {
+    let operation = detect_in_progress_operation(&repo_path)
+        .await
+        .ok_or_else(|| "Nothing to abort: no merge, rebase, or cherry-pick in progress".to_string())?;
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, &operation, "--abort"])
         .output()
         .await
-        .map_err(|e| format!("Failed to stage changes: {}", e))?;
+        .map_err(|e| format!("Failed to abort {}: {}", operation, e))?;
 
-    if !add_output.status.success() {
-        let stderr = String::from_utf8_lossy(&add_output.stderr);
-        return Err(format!("Failed to stage changes: {}", stderr));
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to abort {}: {}", operation, stderr));
     }
 
-    // Commit
-    let commit_output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "commit", "-m", &message])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to commit: {}", e))?;
+    git_status(repo_path).await
+}
 
-    if !commit_output.status.success() {
-        let stderr = String::from_utf8_lossy(&commit_output.stderr);
-        // Check if there's nothing to commit
-        if stderr.contains("nothing to commit") {
-            return Ok("Nothing to commit".to_string());
-        }
-        return Err(format!("Failed to commit: {}", stderr));
+/// Move the current branch to `ref_or_commit`. `mode` is "soft" (keep the index
+/// and working tree), "mixed" (keep the working tree) or "hard" (discard both),
+/// and "hard" is refused unless `confirm_hard` is set.
+#[tauri::command]
+pub async fn git_reset(
+    repo_path: String,
+    ref_or_commit: String,
+    mode: String,
+    confirm_hard: Option<bool>,
+) -> Result<GitStatus, String> {
+    if !matches!(mode.as_str(), "soft" | "mixed" | "hard") {
+        return Err(format!("Invalid reset mode: {}", mode));
+    }
+    if mode == "hard" && !confirm_hard.unwrap_or(false) {
+        return Err("Hard reset discards uncommitted changes; confirm it to continue".to_string());
     }
+    let commit = verify_commit_ref(&repo_path, &ref_or_commit).await?;
 
-    // Get commit hash
-    let hash_output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "rev-parse", "--short", "HEAD"])
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "reset", "--quiet", &format!("--{}", mode), &commit])
         .output()
         .await
-        .map_err(|e| format!("Failed to get commit hash: {}", e))?;
+        .map_err(|e| format!("Failed to reset: {}", e))?;
 
-    let hash = String::from_utf8_lossy(&hash_output.stdout).trim().to_string();
-    Ok(hash)
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to reset: {}", stderr.trim()));
+    }
+
+    git_status(repo_path).await
 }
 
-/// Push changes to remote
-#[tauri::command]
-pub async fn git_push(repo_path: String, branch: String) -> Result<(), String> {
-    // Set upstream and push
+/// Run a git command over `paths`, which are passed after `--` so they're never read as options
+async fn run_git_on_paths(repo_path: &str, args: &[&str], paths: &[String], action: &str) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err(format!("No paths given to {}", action));
+    }
+
     let output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "push", "-u", "origin", &branch])
+        .args(["-C", repo_path])
+        .args(args)
+        .arg("--")
+        .args(paths)
         .output()
         .await
-        .map_err(|e| format!("Failed to push: {}", e))?;
+        .map_err(|e| format!("Failed to {}: {}", action, e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to push: {}", stderr));
+        return Err(format!("Failed to {}: {}", action, stderr.trim()));
     }
 
     Ok(())
 }
 
-/// Create a pull request using GitHub API
+/// Stage the given files
 #[tauri::command]
-pub async fn git_create_pr(
-    repo_full_name: String,
-    head_branch: String,
-    base_branch: String,
-    title: String,
-    body: String,
-) -> Result<String, String> {
-    let token = get_access_token().await
-        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+pub async fn git_stage(repo_path: String, paths: Vec<String>) -> Result<GitStatus, String> {
+    run_git_on_paths(&repo_path, &["add"], &paths, "stage files").await?;
+    git_status(repo_path).await
+}
 
-    let client = reqwest::Client::new();
+/// Unstage the given files, leaving their working tree changes in place
+#[tauri::command]
+pub async fn git_unstage(repo_path: String, paths: Vec<String>) -> Result<GitStatus, String> {
+    let has_head = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "rev-parse", "--verify", "--quiet", "HEAD"])
+        .output()
+        .await
+        .map(|output| output.status.success())
+        .unwrap_or(false);
 
-    #[derive(Serialize)]
-    struct CreatePRRequest {
-        title: String,
-        body: String,
-        head: String,
-        base: String,
-    }
+    // Before the first commit there's nothing to restore from, so drop the entries from the index
+    let args: &[&str] = if has_head { &["restore", "--staged"] } else { &["rm", "--cached", "-r", "--quiet"] };
+    run_git_on_paths(&repo_path, args, &paths, "unstage files").await?;
+    git_status(repo_path).await
+}
 
-    #[derive(Deserialize)]
-    struct CreatePRResponse {
-        html_url: String,
+/// Discard working tree changes to the given files: tracked files are restored
+/// from the index and untracked files are deleted. Staged changes are kept.
+#[tauri::command]
+pub async fn git_discard(repo_path: String, paths: Vec<String>) -> Result<GitStatus, String> {
+    if paths.is_empty() {
+        return Err("No paths given to discard changes".to_string());
     }
 
-    let response = client
-        .post(format!("https://api.github.com/repos/{}/pulls", repo_full_name))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "hatch-desktop")
-        .header("Accept", "application/vnd.github.v3+json")
-        .json(&CreatePRRequest {
-            title,
-            body,
-            head: head_branch,
-            base: base_branch,
+    let repo = std::fs::canonicalize(&repo_path)
+        .map_err(|e| format!("Failed to resolve repository path: {}", e))?;
+    let pathspecs = paths
+        .iter()
+        .map(|path| {
+            let resolved = crate::resolve_path_within_base(&repo, path)?;
+            Ok(resolved.strip_prefix(&repo).unwrap_or(&resolved).to_string_lossy().to_string())
         })
-        .send()
+        .collect::<Result<Vec<String>, String>>()?;
+
+    // Ask git which of these paths are untracked rather than trusting the caller,
+    // so a tracked file is never deleted
+    let status_output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "status", "--porcelain", "-z", "--untracked-files=all", "--"])
+        .args(&pathspecs)
+        .output()
         .await
-        .map_err(|e| format!("Failed to create PR: {}", e))?;
+        .map_err(|e| format!("Failed to get status: {}", e))?;
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("GitHub API error: {}", error_text));
+    if !status_output.status.success() {
+        let stderr = String::from_utf8_lossy(&status_output.stderr);
+        return Err(format!("Failed to get status: {}", stderr.trim()));
     }
 
-    let pr_response: CreatePRResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse PR response: {}", e))?;
+    let status_str = String::from_utf8_lossy(&status_output.stdout);
+    let mut restore: Vec<String> = Vec::new();
+    let mut untracked: Vec<String> = Vec::new();
+    let mut entries = status_str.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (index_status, worktree_status) = (entry.as_bytes()[0], entry.as_bytes()[1]);
+        let file = entry[3..].to_string();
+        // Renames and copies are followed by their original path
+        if matches!(index_status, b'R' | b'C') {
+            entries.next();
+        }
 
-    Ok(pr_response.html_url)
-}
+        match (index_status, worktree_status) {
+            (b'?', b'?') => untracked.push(file),
+            (_, b' ') => {}
+            _ => restore.push(file),
+        }
+    }
 
-/// Create a new GitHub repository
-#[tauri::command]
-pub async fn git_create_github_repo(name: String, is_private: bool) -> Result<Repository, String> {
-    let token = get_access_token().await
-        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    if !restore.is_empty() {
+        run_git_on_paths(&repo_path, &["restore"], &restore, "discard changes").await?;
+    }
 
-    let client = reqwest::Client::new();
+    for file in untracked {
+        let full_path = crate::resolve_path_within_base(&repo, &file)?;
+        std::fs::remove_file(&full_path)
+            .map_err(|e| format!("Failed to delete {}: {}", file, e))?;
+    }
 
-    #[derive(Serialize)]
-    struct CreateRepoRequest {
-        name: String,
-        private: bool,
-        auto_init: bool, // Initialize with README
+    git_status(repo_path).await
+}
+
+/// Commit with the given message. Stages all changes first unless `staged_only`
+/// is set, in which case only what's already in the index is committed.
+#[tauri::command]
+pub async fn git_commit(
+    repo_path: String,
+    message: String,
+    author_name: Option<String>,
+    author_email: Option<String>,
+    sign: Option<bool>,
+    signing_key: Option<String>,
+    staged_only: Option<bool>,
+) -> Result<String, String> {
+    if !staged_only.unwrap_or(false) {
+        stage_all_changes(&repo_path).await?;
     }
 
-    #[derive(Deserialize)]
-    struct CreateRepoResponse {
-        id: i64,
-        name: String,
-        full_name: String,
-        clone_url: String,
-        default_branch: String,
-        private: bool,
+    // Commit, overriding the configured identity for this commit only when asked
+    let mut args = vec!["-C".to_string(), repo_path.clone()];
+    if let Some(name) = author_name.filter(|name| !name.trim().is_empty()) {
+        args.extend(["-c".to_string(), format!("user.name={}", name)]);
+    }
+    if let Some(email) = author_email.filter(|email| !email.trim().is_empty()) {
+        args.extend(["-c".to_string(), format!("user.email={}", email)]);
+    }
+    args.extend(["commit".to_string(), "-m".to_string(), message]);
+    if let Some(flag) = signing_flag(sign, signing_key.as_deref()) {
+        args.push(flag);
     }
 
-    let response = client
-        .post("https://api.github.com/user/repos")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "hatch-desktop")
-        .header("Accept", "application/vnd.github.v3+json")
-        .json(&CreateRepoRequest {
-            name: name.clone(),
-            private: is_private,
-            auto_init: true,
-        })
-        .send()
+    let commit_output = AsyncCommand::new("git")
+        .args(&args)
+        .output()
         .await
-        .map_err(|e| format!("Failed to create repository: {}", e))?;
+        .map_err(|e| format!("Failed to commit: {}", e))?;
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("GitHub API error: {}", error_text));
+    if !commit_output.status.success() {
+        let stderr = String::from_utf8_lossy(&commit_output.stderr);
+        // Check if there's nothing to commit
+        if stderr.contains("nothing to commit") {
+            return Ok("Nothing to commit".to_string());
+        }
+        if is_signing_failure(&stderr) {
+            return Err(format!("Failed to sign commit: {}", stderr.trim()));
+        }
+        return Err(format!("Failed to commit: {}", stderr));
     }
 
-    let repo_response: CreateRepoResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    short_head_hash(&repo_path).await
+}
 
-    // Clone the newly created repo
+/// Write the commit identity to the repository's local config
+#[tauri::command]
+pub async fn git_set_identity(repo_path: String, name: String, email: String) -> Result<(), String> {
+    for (key, value) in [("user.name", &name), ("user.email", &email)] {
+        if value.trim().is_empty() {
+            return Err(format!("Failed to set identity: {} cannot be empty", key));
+        }
+
+        let output = AsyncCommand::new("git")
+            .args(["-C", &repo_path, "config", "--local", key, value])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to set identity: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to set identity: {}", stderr));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fold all current changes into the last commit, keeping its message unless a new one is given
+#[tauri::command]
+pub async fn git_amend_commit(
+    repo_path: String,
+    message: Option<String>,
+    sign: Option<bool>,
+    signing_key: Option<String>,
+) -> Result<String, String> {
+    let head_output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "rev-parse", "--verify", "--quiet", "HEAD"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to amend commit: {}", e))?;
+
+    if !head_output.status.success() {
+        return Err("Nothing to amend: the branch has no commits yet".to_string());
+    }
+
+    stage_all_changes(&repo_path).await?;
+
+    let signing = signing_flag(sign, signing_key.as_deref());
+    let mut args = vec!["-C", repo_path.as_str(), "commit", "--amend"];
+    match message.as_deref() {
+        Some(message) => args.extend(["-m", message]),
+        None => args.push("--no-edit"),
+    }
+    if let Some(flag) = signing.as_deref() {
+        args.push(flag);
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to amend commit: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_signing_failure(&stderr) {
+            return Err(format!("Failed to sign commit: {}", stderr.trim()));
+        }
+        return Err(format!("Failed to amend commit: {}", stderr));
+    }
+
+    short_head_hash(&repo_path).await
+}
+
+/// `-S` flag for signed commits; a key selects a specific GPG key id or SSH key path.
+/// Whether GPG or SSH is used follows the repo's `gpg.format` config.
+fn signing_flag(sign: Option<bool>, signing_key: Option<&str>) -> Option<String> {
+    let key = signing_key.map(str::trim).filter(|key| !key.is_empty());
+    if !sign.unwrap_or(false) && key.is_none() {
+        return None;
+    }
+    Some(match key {
+        Some(key) => format!("--gpg-sign={}", key),
+        None => "-S".to_string(),
+    })
+}
+
+/// Recognize gpg / ssh-keygen signing errors so they can be passed through verbatim
+fn is_signing_failure(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("gpg failed to sign")
+        || lower.contains("cannot run gpg")
+        || lower.contains("ssh-keygen")
+        || lower.contains("signing failed")
+}
+
+async fn stage_all_changes(repo_path: &str) -> Result<(), String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "add", "-A"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to stage changes: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to stage changes: {}", stderr));
+    }
+
+    Ok(())
+}
+
+async fn short_head_hash(repo_path: &str) -> Result<String, String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--short", "HEAD"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to get commit hash: {}", e))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Error prefix for a push the remote rejected because the branch has moved on,
+/// so the UI can offer to pull or rebase
+pub const PUSH_REJECTED_NON_FAST_FORWARD: &str = "Push rejected (non-fast-forward)";
+
+/// Error prefix for a force-with-lease push refused because the remote branch
+/// changed since it was last fetched (someone else pushed)
+pub const PUSH_REJECTED_STALE_LEASE: &str = "Push rejected (stale lease)";
+
+/// Payload of the `push-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct PushProgress {
+    pub repo_path: String,
+    pub branch: String,
+    /// Git's phase label, e.g. "Writing objects"
+    pub phase: String,
+    pub percent: u8,
+}
+
+/// Push changes to remote, emitting `push-progress` events as git reports them.
+/// `force_with_lease` overwrites the remote branch (e.g. after an amend or
+/// rebase) only if nobody else has pushed to it since the last fetch.
+#[tauri::command]
+pub async fn git_push(
+    app: tauri::AppHandle,
+    repo_path: String,
+    branch: String,
+    force_with_lease: Option<bool>,
+) -> Result<(), String> {
+    push_with_progress(Some(&app), &repo_path, &branch, force_with_lease.unwrap_or(false)).await
+}
+
+/// Push `branch` to origin, setting upstream. With an app handle, each progress
+/// line git writes to stderr is emitted as a `push-progress` event.
+pub(crate) async fn push_with_progress(
+    app: Option<&tauri::AppHandle>,
+    repo_path: &str,
+    branch: &str,
+    force_with_lease: bool,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    // --progress because git only reports progress to a terminal by default.
+    // Never plain --force: the lease keeps us from clobbering someone else's push.
+    let mut args = vec!["-C", repo_path, "push", "--progress", "-u"];
+    if force_with_lease {
+        args.push("--force-with-lease");
+    }
+    args.extend(["origin", branch]);
+    let mut child = AsyncCommand::new("git")
+        .args(&args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to push: {}", e))?;
+
+    let stderr = child.stderr.take().ok_or("Failed to push: stderr not captured")?;
+    let collected = read_git_progress(stderr, |phase, percent| {
+        if let Some(app) = app {
+            let _ = app.emit("push-progress", PushProgress {
+                repo_path: repo_path.to_string(),
+                branch: branch.to_string(),
+                phase,
+                percent,
+            });
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to read push output: {}", e))?;
+
+    let status = child.wait().await.map_err(|e| format!("Failed to push: {}", e))?;
+    if status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&collected);
+    if force_with_lease && stderr.lines().any(|line| line.contains("[rejected]") && line.contains("stale info")) {
+        return Err(format!(
+            "{}: someone else pushed to '{}' since your last fetch. Fetch and review their changes before pushing again.",
+            PUSH_REJECTED_STALE_LEASE, branch
+        ));
+    }
+    if is_non_fast_forward_rejection(&stderr) {
+        return Err(format!(
+            "{}: the remote branch has commits you don't have locally. Pull or rebase, then push again.",
+            PUSH_REJECTED_NON_FAST_FORWARD
+        ));
+    }
+    // Only keep the final state of redrawn progress lines in the error
+    let message: Vec<&str> = stderr
+        .lines()
+        .filter_map(|line| line.rsplit('\r').next())
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    Err(format!("Failed to push: {}", message.join("\n")))
+}
+
+/// Read a git command's stderr to the end, calling `on_progress` for each new
+/// phase/percentage it reports, and return everything it wrote
+async fn read_git_progress(
+    mut stderr: impl tokio::io::AsyncRead + Unpin,
+    mut on_progress: impl FnMut(String, u8),
+) -> std::io::Result<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
+
+    let mut collected = Vec::new();
+    let mut pending = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let mut last_reported: Option<(String, u8)> = None;
+
+    loop {
+        let read = stderr.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        collected.extend_from_slice(&buffer[..read]);
+
+        // Progress lines are redrawn with '\r', so treat it as a line break too
+        for &byte in &buffer[..read] {
+            if byte != b'\r' && byte != b'\n' {
+                pending.push(byte);
+                continue;
+            }
+            let line = String::from_utf8_lossy(&pending).to_string();
+            pending.clear();
+            let Some((phase, percent)) = parse_git_progress(&line) else {
+                continue;
+            };
+            if last_reported.as_ref() == Some(&(phase.clone(), percent)) {
+                continue;
+            }
+            on_progress(phase.clone(), percent);
+            last_reported = Some((phase, percent));
+        }
+    }
+
+    Ok(collected)
+}
+
+/// Parse a git progress line like `Writing objects:  45% (9/20)` into its phase
+/// and percentage. A `remote: ` prefix is dropped.
+pub(crate) fn parse_git_progress(line: &str) -> Option<(String, u8)> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let (phase, rest) = line.split_once(':')?;
+    let percent = rest.trim_start().split('%').next()?.trim().parse::<u8>().ok()?;
+    if phase.is_empty() || percent > 100 || !rest.contains('%') {
+        return None;
+    }
+    Some((phase.trim().to_string(), percent))
+}
+
+fn is_non_fast_forward_rejection(stderr: &str) -> bool {
+    stderr.lines().any(|line| {
+        line.contains("[rejected]") && (line.contains("non-fast-forward") || line.contains("fetch first"))
+    })
+}
+
+/// Switch the worktree to another branch, optionally creating it first
+#[tauri::command]
+pub async fn git_switch_branch(repo_path: String, branch: String, create: bool) -> Result<GitStatus, String> {
+    // A leading '-' would be parsed as an option to git switch
+    if branch.trim().is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch name: {}", branch));
+    }
+
+    let mut args = vec!["-C", repo_path.as_str(), "switch"];
+    if create {
+        args.push("-c");
+    }
+    args.push(branch.as_str());
+
+    let output = AsyncCommand::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to switch branch: {}", e))?;
+
+    if !output.status.success() {
+        // git switch refuses up front when local changes would be overwritten,
+        // so the worktree is left untouched
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let blocking = parse_blocking_files(&stderr);
+        if !blocking.is_empty() {
+            return Err(format!(
+                "Failed to switch branch: local changes would be overwritten in {}",
+                blocking.join(", ")
+            ));
+        }
+        return Err(format!("Failed to switch branch: {}", stderr));
+    }
+
+    git_status(repo_path).await
+}
+
+/// Outcome of `git_rebase_onto`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebaseResult {
+    /// False when the rebase stopped on conflicts. Resolve them and continue,
+    /// or back out with `git_abort`.
+    pub completed: bool,
+    pub conflicted: Vec<String>,
+    pub status: GitStatus,
+}
+
+/// Fetch origin, then rebase the worktree's current branch onto `base_ref`
+/// (e.g. "origin/main")
+#[tauri::command]
+pub async fn git_rebase_onto(repo_path: String, base_ref: String) -> Result<RebaseResult, String> {
+    if let Some(operation) = detect_in_progress_operation(&repo_path).await {
+        return Err(format!("Cannot rebase: a {} is already in progress", operation));
+    }
+
+    let has_origin = git_stdout(&["-C", &repo_path, "remote"])
+        .await
+        .is_some_and(|remotes| remotes.lines().any(|remote| remote == "origin"));
+    if has_origin {
+        git_fetch(repo_path.clone()).await?;
+    }
+    verify_commit_ref(&repo_path, &base_ref).await?;
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "rebase", &base_ref])
+        // Never wait on an editor for a message
+        .env("GIT_EDITOR", "true")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to rebase: {}", e))?;
+
+    if !output.status.success() && detect_in_progress_operation(&repo_path).await.as_deref() != Some("rebase") {
+        // Refused before starting, e.g. uncommitted changes
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to rebase: {}", stderr.trim()));
+    }
+
+    let status = git_status(repo_path).await?;
+    Ok(RebaseResult {
+        completed: output.status.success(),
+        conflicted: status.conflicted.clone(),
+        status,
+    })
+}
+
+/// Outcome of `git_cherry_pick`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CherryPickResult {
+    /// False when the pick stopped on conflicts. Resolve them and continue,
+    /// or back out with `git_abort`.
+    pub completed: bool,
+    /// Short hash of the new commit when the pick completed
+    pub commit_hash: Option<String>,
+    pub conflicted: Vec<String>,
+    pub status: GitStatus,
+}
+
+/// Apply the changes from `commit_sha` onto the worktree's current branch
+#[tauri::command]
+pub async fn git_cherry_pick(repo_path: String, commit_sha: String) -> Result<CherryPickResult, String> {
+    let commit = verify_commit_ref(&repo_path, &commit_sha)
+        .await
+        .map_err(|_| format!("Unknown commit: {}", commit_sha))?;
+    if let Some(operation) = detect_in_progress_operation(&repo_path).await {
+        return Err(format!("Cannot cherry-pick: a {} is already in progress", operation));
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "cherry-pick", &commit])
+        .env("GIT_EDITOR", "true")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to cherry-pick: {}", e))?;
+
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if detect_in_progress_operation(&repo_path).await.as_deref() != Some("cherry-pick") {
+            return Err(format!("Failed to cherry-pick: {}", stderr.trim()));
+        }
+        // A pick whose changes are already on the branch stops with nothing to
+        // commit; skip it rather than leave an empty cherry-pick in progress
+        if stdout.contains("is now empty") || stderr.contains("is now empty") {
+            let _ = AsyncCommand::new("git")
+                .args(["-C", &repo_path, "cherry-pick", "--skip"])
+                .output()
+                .await;
+            return Err(format!("Nothing to cherry-pick: {} is already applied", commit_sha));
+        }
+    }
+
+    let commit_hash = if output.status.success() {
+        Some(short_head_hash(&repo_path).await?)
+    } else {
+        None
+    };
+    let status = git_status(repo_path).await?;
+    Ok(CherryPickResult {
+        completed: output.status.success(),
+        commit_hash,
+        conflicted: status.conflicted.clone(),
+        status,
+    })
+}
+
+/// Collect the tab-indented file list git prints when local changes block a checkout
+fn parse_blocking_files(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .filter(|line| line.starts_with('\t'))
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub short_hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub timestamp: i64, // Unix seconds
+    pub subject: String,
+}
+
+/// List recent commits on the current branch, or on `branch` when given
+#[tauri::command]
+pub async fn git_log(repo_path: String, limit: Option<u32>, branch: Option<String>) -> Result<Vec<CommitInfo>, String> {
+    let limit = limit.unwrap_or(50).to_string();
+    let mut args = vec![
+        "-C",
+        repo_path.as_str(),
+        "log",
+        "--pretty=format:%H%x00%an%x00%ae%x00%at%x00%s",
+        "-n",
+        limit.as_str(),
+    ];
+    if let Some(branch) = branch.as_deref() {
+        if branch.starts_with('-') {
+            return Err(format!("Invalid branch name: {}", branch));
+        }
+        args.push(branch);
+    }
+    args.push("--");
+
+    let output = AsyncCommand::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to get log: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // A freshly initialized repo has no history yet
+        if stderr.contains("does not have any commits yet") {
+            return Ok(Vec::new());
+        }
+        return Err(format!("Failed to get log: {}", stderr));
+    }
+
+    Ok(parse_log_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse NUL-separated `git log` records, one commit per line
+pub(crate) fn parse_log_output(output: &str) -> Vec<CommitInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\0');
+            let hash = fields.next()?.to_string();
+            let author_name = fields.next()?.to_string();
+            let author_email = fields.next()?.to_string();
+            let timestamp = fields.next()?.parse().unwrap_or(0);
+            let subject = fields.next().unwrap_or_default().to_string();
+            if hash.is_empty() {
+                return None;
+            }
+
+            Some(CommitInfo {
+                short_hash: hash.chars().take(7).collect(),
+                hash,
+                author_name,
+                author_email,
+                timestamp,
+                subject,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchInfo {
+    pub name: String, // "feature/x" for local, "origin/feature/x" for remote
+    pub is_current: bool,
+    pub is_remote: bool,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// List local branches, and remote-tracking branches when `include_remote` is set
+#[tauri::command]
+pub async fn git_list_branches(repo_path: String, include_remote: bool) -> Result<Vec<BranchInfo>, String> {
+    let mut args = vec![
+        "-C",
+        repo_path.as_str(),
+        "for-each-ref",
+        "--format=%(refname)%00%(HEAD)%00%(upstream:short)%00%(upstream:track,nobracket)",
+        "refs/heads",
+    ];
+    if include_remote {
+        args.push("refs/remotes");
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to list branches: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to list branches: {}", stderr));
+    }
+
+    Ok(parse_branch_refs(&String::from_utf8_lossy(&output.stdout)))
+}
+
+pub(crate) fn parse_branch_refs(output: &str) -> Vec<BranchInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let refname = fields.next()?;
+            let is_current = fields.next()? == "*";
+            let upstream = fields.next().filter(|u| !u.is_empty()).map(|u| u.to_string());
+            let track = fields.next().unwrap_or_default();
+
+            let (name, is_remote) = if let Some(name) = refname.strip_prefix("refs/heads/") {
+                (name, false)
+            } else {
+                (refname.strip_prefix("refs/remotes/")?, true)
+            };
+            // Skip symbolic refs like origin/HEAD
+            if is_remote && name.ends_with("/HEAD") {
+                return None;
+            }
+
+            let (ahead, behind) = parse_track_counts(track);
+            Some(BranchInfo {
+                name: name.to_string(),
+                is_current,
+                is_remote,
+                upstream,
+                ahead,
+                behind,
+            })
+        })
+        .collect()
+}
+
+/// Parse `%(upstream:track,nobracket)` output such as "ahead 2, behind 1" or "gone"
+pub(crate) fn parse_track_counts(track: &str) -> (u32, u32) {
+    let mut ahead = 0;
+    let mut behind = 0;
+    for part in track.split(',') {
+        let mut words = part.split_whitespace();
+        match (words.next(), words.next().and_then(|n| n.parse().ok())) {
+            (Some("ahead"), Some(n)) => ahead = n,
+            (Some("behind"), Some(n)) => behind = n,
+            _ => {}
+        }
+    }
+    (ahead, behind)
+}
+
+/// A single ref line reported by `git fetch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchedRef {
+    pub status: String, // "new", "updated", "forced", "pruned", "tag"
+    pub from: Option<String>,
+    pub to: String,
+    pub summary: Option<String>,
+}
+
+/// A configured remote
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub fetch_url: Option<String>,
+    pub push_url: Option<String>,
+}
+
+/// List the repository's remotes in the order git reports them
+#[tauri::command]
+pub async fn git_list_remotes(repo_path: String) -> Result<Vec<RemoteInfo>, String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "remote", "-v"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to list remotes: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to list remotes: {}", stderr.trim()));
+    }
+
+    Ok(parse_remotes(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git remote -v` lines like `origin\thttps://host/repo.git (fetch)`
+pub(crate) fn parse_remotes(output: &str) -> Vec<RemoteInfo> {
+    let mut remotes: Vec<RemoteInfo> = Vec::new();
+
+    for line in output.lines() {
+        let Some((name, rest)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some((url, kind)) = rest.rsplit_once(' ') else {
+            continue;
+        };
+
+        let index = match remotes.iter().position(|remote| remote.name == name) {
+            Some(index) => index,
+            None => {
+                remotes.push(RemoteInfo {
+                    name: name.to_string(),
+                    fetch_url: None,
+                    push_url: None,
+                });
+                remotes.len() - 1
+            }
+        };
+        match kind {
+            "(fetch)" => remotes[index].fetch_url = Some(url.to_string()),
+            "(push)" => remotes[index].push_url = Some(url.to_string()),
+            _ => {}
+        }
+    }
+
+    remotes
+}
+
+/// Add a remote, e.g. `upstream` for the repository a fork came from
+#[tauri::command]
+pub async fn git_add_remote(repo_path: String, name: String, url: String) -> Result<Vec<RemoteInfo>, String> {
+    let name = name.trim();
+    let url = url.trim();
+    if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+        return Err(format!("Invalid remote name: {}", name));
+    }
+    if url.is_empty() || url.starts_with('-') {
+        return Err(format!("Invalid remote URL: {}", url));
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "remote", "add", name, url])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to add remote: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to add remote: {}", stderr.trim()));
+    }
+
+    git_list_remotes(repo_path).await
+}
+
+/// Remove a remote along with its remote-tracking branches
+#[tauri::command]
+pub async fn git_remove_remote(repo_path: String, name: String) -> Result<Vec<RemoteInfo>, String> {
+    if name.trim().is_empty() || name.starts_with('-') {
+        return Err(format!("Invalid remote name: {}", name));
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "remote", "remove", &name])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to remove remote: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to remove remote: {}", stderr.trim()));
+    }
+
+    git_list_remotes(repo_path).await
+}
+
+/// Fetch from origin, pruning remote-tracking refs that no longer exist
+#[tauri::command]
+pub async fn git_fetch(repo_path: String) -> Result<Vec<FetchedRef>, String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", &repo_path, "fetch", "--prune", "origin"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        return Err(format!("Failed to fetch: {}", stderr));
+    }
+
+    // git fetch reports ref updates on stderr
+    Ok(parse_fetch_output(&stderr))
+}
+
+/// Parse the ref update lines from `git fetch` output, e.g.
+///    abc1234..def5678  main       -> origin/main
+///  * [new branch]      feature    -> origin/feature
+///  - [deleted]         (none)     -> origin/old
+pub(crate) fn parse_fetch_output(output: &str) -> Vec<FetchedRef> {
+    let mut refs = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim_end();
+        let mut chars = line.chars();
+        if chars.next() != Some(' ') {
+            continue;
+        }
+        let flag = chars.next().unwrap_or(' ');
+        let rest = chars.as_str().trim();
+
+        let (left, to) = match rest.split_once(" -> ") {
+            Some(parts) => parts,
+            None => continue,
+        };
+        // Strip trailing annotations like "(forced update)"
+        let to = to.split("  ").next().unwrap_or(to).trim().to_string();
+
+        let (summary, from) = if let Some(bracketed) = left.strip_prefix('[') {
+            match bracketed.split_once(']') {
+                Some((summary, from)) => (summary.trim().to_string(), from.trim().to_string()),
+                None => continue,
+            }
+        } else {
+            match left.split_once(char::is_whitespace) {
+                Some((summary, from)) => (summary.trim().to_string(), from.trim().to_string()),
+                None => continue,
+            }
+        };
+
+        let status = match flag {
+            '*' if summary.contains("tag") => "tag",
+            '*' => "new",
+            '+' => "forced",
+            '-' => "pruned",
+            't' => "tag",
+            ' ' => "updated",
+            _ => continue,
+        };
+
+        refs.push(FetchedRef {
+            status: status.to_string(),
+            from: if from.is_empty() || from == "(none)" { None } else { Some(from) },
+            to,
+            summary: if summary.is_empty() { None } else { Some(summary) },
+        });
+    }
+
+    refs
+}
+
+/// Create a pull request using GitHub API
+#[tauri::command]
+pub async fn git_create_pr(
+    repo_full_name: String,
+    head_branch: String,
+    base_branch: String,
+    title: String,
+    body: String,
+) -> Result<String, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+
+    let client = reqwest::Client::new();
+
+    #[derive(Serialize)]
+    struct CreatePRRequest {
+        title: String,
+        body: String,
+        head: String,
+        base: String,
+    }
+
+    #[derive(Deserialize)]
+    struct CreatePRResponse {
+        html_url: String,
+    }
+
+    let request = client
+        .post(format!("{}/repos/{}/pulls", api_base, repo_full_name))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3+json")
+        .json(&CreatePRRequest {
+            title,
+            body,
+            head: head_branch,
+            base: base_branch,
+        });
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to create PR: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
+    }
+
+    let pr_response: CreatePRResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse PR response: {}", e))?;
+
+    Ok(pr_response.html_url)
+}
+
+/// What `git_ship` should commit, push and open a pull request for
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipRequest {
+    pub repo_path: String,
+    pub repo_full_name: String,
+    pub branch: String,
+    pub message: String,
+    pub base_branch: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// How far `git_ship` got. Steps after `failed_step` weren't attempted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShipResult {
+    pub committed: bool,
+    /// Short hash of the new commit, or `None` if there was nothing to commit
+    pub commit_hash: Option<String>,
+    pub pushed: bool,
+    pub pr_url: Option<String>,
+    /// "commit", "push" or "pr" when a step failed
+    pub failed_step: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Commit all changes, push the branch and open a pull request, stopping at
+/// the first step that fails
+#[tauri::command]
+pub async fn git_ship(app: tauri::AppHandle, request: ShipRequest) -> Result<ShipResult, String> {
+    Ok(ship_changes(Some(&app), request).await)
+}
+
+/// `git_ship`'s steps. Failures are reported in the result rather than as an
+/// error so the caller knows what already happened (e.g. committed but not pushed).
+pub(crate) async fn ship_changes(app: Option<&tauri::AppHandle>, request: ShipRequest) -> ShipResult {
+    let mut result = ShipResult::default();
+    let fail = |mut result: ShipResult, step: &str, error: String| {
+        result.failed_step = Some(step.to_string());
+        result.error = Some(error);
+        result
+    };
+
+    match git_commit(request.repo_path.clone(), request.message, None, None, None, None, None).await {
+        Ok(hash) => {
+            result.committed = true;
+            result.commit_hash = (hash != "Nothing to commit").then_some(hash);
+        }
+        Err(error) => return fail(result, "commit", error),
+    }
+
+    if let Err(error) = push_with_progress(app, &request.repo_path, &request.branch, false).await {
+        return fail(result, "push", error);
+    }
+    result.pushed = true;
+
+    match git_create_pr(request.repo_full_name, request.branch, request.base_branch, request.title, request.body).await {
+        Ok(url) => result.pr_url = Some(url),
+        Err(error) => return fail(result, "pr", error),
+    }
+    result
+}
+
+/// Create a new GitHub repository
+#[tauri::command]
+pub async fn git_create_github_repo(name: String, is_private: bool) -> Result<Repository, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+
+    let client = reqwest::Client::new();
+
+    #[derive(Serialize)]
+    struct CreateRepoRequest {
+        name: String,
+        private: bool,
+        auto_init: bool, // Initialize with README
+    }
+
+    #[derive(Deserialize)]
+    struct CreateRepoResponse {
+        id: i64,
+        name: String,
+        full_name: String,
+        clone_url: String,
+        default_branch: String,
+        private: bool,
+    }
+
+    let request = client
+        .post(format!("{}/user/repos", api_base))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3+json")
+        .json(&CreateRepoRequest {
+            name: name.clone(),
+            private: is_private,
+            auto_init: true,
+        });
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to create repository: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
+    }
+
+    let repo_response: CreateRepoResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    // Clone the newly created repo
     let workspaces_dir = get_workspaces_dir()?;
     let local_path = workspaces_dir.join(&name);
 
@@ -452,6 +1832,7 @@ pub async fn git_create_github_repo(name: String, is_private: bool) -> Result<Re
         local_path: local_path.to_string_lossy().to_string(),
         default_branch: repo_response.default_branch,
         is_private: repo_response.private,
+        is_shallow: false,
     })
 }
 
@@ -591,9 +1972,125 @@ pub async fn git_delete_workspace_branch(repo_path: String, branch_name: String,
     Ok(())
 }
 
-/// Get the diff for a repository
+/// Commits to diff between, already verified. `target` of None means the working tree.
+struct DiffRange {
+    base: String,
+    target: Option<String>,
+}
+
+/// Resolve a ref to its commit SHA, rejecting anything `rev-parse` doesn't know
+async fn verify_commit_ref(repo_path: &str, git_ref: &str) -> Result<String, String> {
+    if git_ref.trim().is_empty() || git_ref.starts_with('-') {
+        return Err(format!("Invalid ref: {}", git_ref));
+    }
+
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", git_ref)])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to resolve ref: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Unknown ref: {}", git_ref));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Build the range for `base_ref`/`target_ref`. With both set, the base is their
+/// merge base, matching `git diff <base>...<target>`; with only a base, the
+/// working tree is compared against it. Returns None when neither is given.
+async fn resolve_diff_range(
+    repo_path: &str,
+    base_ref: Option<String>,
+    target_ref: Option<String>,
+) -> Result<Option<DiffRange>, String> {
+    let base_ref = base_ref.filter(|r| !r.is_empty());
+    let target_ref = target_ref.filter(|r| !r.is_empty());
+
+    let (base_ref, target_ref) = match (base_ref, target_ref) {
+        (None, None) => return Ok(None),
+        (Some(base), target) => (base, target),
+        (None, Some(target)) => ("HEAD".to_string(), Some(target)),
+    };
+
+    let base = verify_commit_ref(repo_path, &base_ref).await?;
+    let Some(target_ref) = target_ref else {
+        return Ok(Some(DiffRange { base, target: None }));
+    };
+    let target = verify_commit_ref(repo_path, &target_ref).await?;
+
+    let merge_base = merge_base_of_commits(repo_path, &base, &target)
+        .await?
+        .ok_or_else(|| format!("{} and {} have no common ancestor", base_ref, target_ref))?;
+
+    Ok(Some(DiffRange {
+        base: merge_base,
+        target: Some(target),
+    }))
+}
+
+/// Commit sha of the best common ancestor of two refs, the base of `a...b` diffs
+#[tauri::command]
+pub async fn git_merge_base(repo_path: String, ref_a: String, ref_b: String) -> Result<String, String> {
+    let a = verify_commit_ref(&repo_path, &ref_a).await?;
+    let b = verify_commit_ref(&repo_path, &ref_b).await?;
+    merge_base_of_commits(&repo_path, &a, &b)
+        .await?
+        .ok_or_else(|| format!("{} and {} have no common ancestor", ref_a, ref_b))
+}
+
+/// `git merge-base` of two verified commits, or `None` if their histories never meet
+async fn merge_base_of_commits(repo_path: &str, a: &str, b: &str) -> Result<Option<String>, String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "merge-base", a, b])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to find merge base: {}", e))?;
+
+    if output.status.success() {
+        return Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()));
+    }
+    // Exit code 1 with no output means there's no common ancestor
+    if output.status.code() == Some(1) && output.stderr.is_empty() {
+        return Ok(None);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(format!("Failed to find merge base: {}", stderr.trim()))
+}
+
+/// Contents of `path` at `rev`, or None if it doesn't exist there
+async fn show_blob(repo_path: &str, rev: &str, path: &str) -> Option<Vec<u8>> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "show", &format!("{}:{}", rev, path)])
+        .output()
+        .await
+        .ok()?;
+
+    output.status.success().then_some(output.stdout)
+}
+
+/// Get the diff for a repository. Shows staged and unstaged changes against HEAD
+/// unless `base_ref`/`target_ref` are given.
 #[tauri::command]
-pub async fn git_diff(repo_path: String) -> Result<String, String> {
+pub async fn git_diff(repo_path: String, base_ref: Option<String>, target_ref: Option<String>) -> Result<String, String> {
+    if let Some(range) = resolve_diff_range(&repo_path, base_ref, target_ref).await? {
+        let mut args = vec!["-C".to_string(), repo_path.clone(), "diff".to_string(), range.base];
+        args.extend(range.target);
+
+        let output = AsyncCommand::new("git")
+            .args(&args)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to get diff: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to get diff: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
     // Get both staged and unstaged diff
     let staged = AsyncCommand::new("git")
         .args(["-C", &repo_path, "diff", "--cached"])
@@ -616,6 +2113,7 @@ pub async fn git_diff(repo_path: String) -> Result<String, String> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileChange {
     pub path: String,
+    pub old_path: Option<String>, // Set for renames; `path` is the new location
     pub additions: u32,
     pub deletions: u32,
     pub status: String, // "modified", "added", "deleted", "renamed", "untracked"
@@ -643,7 +2141,7 @@ pub async fn git_diff_stats(repo_path: String) -> Result<Vec<FileChange>, String
         }
         let index_status = line.chars().next().unwrap_or(' ');
         let worktree_status = line.chars().nth(1).unwrap_or(' ');
-        let file = line[3..].to_string();
+        let (file, _) = split_porcelain_rename(&line[3..]);
 
         let status = match (index_status, worktree_status) {
             ('?', '?') => "untracked",
@@ -657,7 +2155,7 @@ pub async fn git_diff_stats(repo_path: String) -> Result<Vec<FileChange>, String
 
     // Get numstat for additions/deletions of tracked files
     let numstat_output = AsyncCommand::new("git")
-        .args(["-C", &repo_path, "diff", "--numstat", "HEAD"])
+        .args(["-C", &repo_path, "diff", "--numstat", "-M", "HEAD"])
         .output()
         .await
         .map_err(|e| format!("Failed to get diff numstat: {}", e))?;
@@ -669,13 +2167,15 @@ pub async fn git_diff_stats(repo_path: String) -> Result<Vec<FileChange>, String
         if parts.len() >= 3 {
             let additions = parts[0].parse().unwrap_or(0);
             let deletions = parts[1].parse().unwrap_or(0);
-            let path = parts[2].to_string();
+            let (path, old_path) = split_numstat_rename(parts[2]);
 
-            let status = file_statuses.get(&path).cloned().unwrap_or_else(|| "modified".to_string());
-            file_statuses.remove(&path);
+            let status = file_statuses.remove(&path).unwrap_or_else(|| {
+                if old_path.is_some() { "renamed" } else { "modified" }.to_string()
+            });
 
             changes.push(FileChange {
                 path,
+                old_path,
                 additions,
                 deletions,
                 status,
@@ -698,6 +2198,7 @@ pub async fn git_diff_stats(repo_path: String) -> Result<Vec<FileChange>, String
 
             changes.push(FileChange {
                 path,
+                old_path: None,
                 additions,
                 deletions: 0,
                 status,
@@ -708,84 +2209,864 @@ pub async fn git_diff_stats(repo_path: String) -> Result<Vec<FileChange>, String
     Ok(changes)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FileEntry {
-    pub name: String,
-    pub path: String,
-    pub is_directory: bool,
-    pub children: Option<Vec<FileEntry>>,
+/// Split a `status --porcelain` path into (new path, old path) for `old -> new` renames
+fn split_porcelain_rename(raw: &str) -> (String, Option<String>) {
+    match raw.split_once(" -> ") {
+        Some((old, new)) => (new.to_string(), Some(old.to_string())),
+        None => (raw.to_string(), None),
+    }
+}
+
+/// Split a `diff --numstat` path into (new path, old path). Renames come either as
+/// `old => new` or with the common parts factored out, e.g. `src/{old => new}/lib.rs`.
+fn split_numstat_rename(raw: &str) -> (String, Option<String>) {
+    if let (Some(open), Some(close)) = (raw.find('{'), raw.rfind('}')) {
+        if open < close {
+            if let Some((old_part, new_part)) = raw[open + 1..close].split_once(" => ") {
+                let prefix = &raw[..open];
+                let suffix = &raw[close + 1..];
+                // An empty side leaves a doubled separator, e.g. `src/{ => sub}/lib.rs`
+                let join = |middle: &str| format!("{}{}{}", prefix, middle, suffix).replace("//", "/");
+                return (join(new_part), Some(join(old_part)));
+            }
+        }
+    }
+
+    match raw.split_once(" => ") {
+        Some((old, new)) => (new.to_string(), Some(old.to_string())),
+        None => (raw.to_string(), None),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub name: String,
+    pub path: String,
+    pub is_directory: bool,
+    pub children: Option<Vec<FileEntry>>,
+    pub has_children: bool,
+    pub git_status: Option<String>, // "modified", "untracked", "staged", "deleted"; "has_changes" for directories
+    /// File size in bytes; None for directories or when metadata wasn't requested
+    pub size: Option<u64>,
+    /// Last modified time (unix ms); None when metadata wasn't requested
+    pub modified: Option<u64>,
+}
+
+/// List all files in a directory recursively. Symlinked directories are followed
+/// unless `follow_symlinks` is false; either way each directory is descended at
+/// most once, so symlink loops can't recurse. `include_metadata` fills in sizes
+/// and modified times at the cost of a stat per entry.
+///
+/// Entries named in `ignore_names` (e.g. ".venv", "dist") are skipped on top of
+/// `DEFAULT_IGNORED_NAMES`, as are entries matching `ignore_globs`. A glob with
+/// a `/` is matched against the path relative to `dir_path`, otherwise against
+/// the entry's name.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn list_directory_files(
+    dir_path: String,
+    max_depth: Option<u32>,
+    show_hidden: Option<bool>,
+    respect_gitignore: Option<bool>,
+    follow_symlinks: Option<bool>,
+    include_metadata: Option<bool>,
+    ignore_names: Option<Vec<String>>,
+    ignore_globs: Option<Vec<String>>,
+) -> Result<Vec<FileEntry>, String> {
+    let path = PathBuf::from(&dir_path);
+    if !path.exists() {
+        return Err(format!("Directory does not exist: {}", dir_path));
+    }
+    let ignore_globs = ignore_globs
+        .unwrap_or_default()
+        .iter()
+        .map(|pattern| glob::Pattern::new(pattern).map_err(|e| format!("Invalid ignore pattern '{}': {}", pattern, e)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let depth = max_depth.unwrap_or(10);
+    let include_hidden = show_hidden.unwrap_or(false);
+    // Only consult .gitignore when the directory is actually inside a work tree
+    let use_gitignore = respect_gitignore.unwrap_or(false) && is_inside_work_tree(&path).await;
+
+    // The walk stats every entry and talks to git synchronously; keep it off
+    // the async runtime
+    tokio::task::spawn_blocking(move || {
+        let mut walk = DirWalk {
+            base_path: &path,
+            show_hidden: include_hidden,
+            use_gitignore,
+            ignore_checker: if use_gitignore { GitIgnoreChecker::spawn(&path) } else { None },
+            follow_symlinks: follow_symlinks.unwrap_or(true),
+            include_metadata: include_metadata.unwrap_or(false),
+            ignore_names: ignore_names.unwrap_or_default().into_iter().collect(),
+            ignore_globs,
+            visited: std::collections::HashSet::new(),
+        };
+        if let Ok(canonical) = std::fs::canonicalize(&path) {
+            walk.visited.insert(canonical);
+        }
+        list_dir_recursive(&mut walk, &path, depth)
+    })
+    .await
+    .map_err(|e| format!("Directory listing failed: {}", e))?
+}
+
+/// Directories the file tree skips when .gitignore rules aren't in use
+const DEFAULT_IGNORED_NAMES: &[&str] = &["node_modules", "target"];
+
+/// Settings and state shared across one recursive directory listing
+struct DirWalk<'a> {
+    base_path: &'a Path,
+    show_hidden: bool,
+    use_gitignore: bool,
+    /// One `git check-ignore` process answering for the whole walk
+    ignore_checker: Option<GitIgnoreChecker>,
+    follow_symlinks: bool,
+    include_metadata: bool,
+    ignore_names: std::collections::HashSet<String>,
+    ignore_globs: Vec<glob::Pattern>,
+    /// Canonical paths of directories already descended into
+    visited: std::collections::HashSet<PathBuf>,
+}
+
+impl DirWalk<'_> {
+    fn is_glob_ignored(&self, name: &str, relative_path: &str) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let relative_path = relative_path.replace('\\', "/");
+        self.ignore_globs.iter().any(|pattern| {
+            if pattern.as_str().contains('/') {
+                pattern.matches_with(&relative_path, options)
+            } else {
+                pattern.matches_with(name, options)
+            }
+        })
+    }
+}
+
+/// A directory entry that passed the walk's filters
+struct VisibleEntry {
+    entry: std::fs::DirEntry,
+    name: String,
+    relative_path: String,
+}
+
+impl DirWalk<'_> {
+    /// The entries of `dir` the listing shows: hidden, ignored and excluded
+    /// entries are filtered out
+    fn visible_entries(&mut self, dir: &Path) -> Result<Vec<VisibleEntry>, String> {
+        let mut dir_entries = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}", e))? {
+            dir_entries.push(entry.map_err(|e| format!("Failed to read entry: {}", e))?);
+        }
+
+        let ignored = self.git_ignored(&dir_entries);
+        let mut visible = Vec::new();
+        for entry in dir_entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Skip hidden files unless show_hidden is true
+            if !self.show_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            if name == ".git" || ignored.contains(&path) {
+                continue;
+            }
+
+            // Without .gitignore rules, fall back to skipping well-known large directories
+            if !self.use_gitignore && DEFAULT_IGNORED_NAMES.contains(&name.as_str()) {
+                continue;
+            }
+
+            let relative_path = path.strip_prefix(self.base_path)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| name.clone());
+
+            if self.ignore_names.contains(&name) || self.is_glob_ignored(&name, &relative_path) {
+                continue;
+            }
+
+            visible.push(VisibleEntry { entry, name, relative_path });
+        }
+        Ok(visible)
+    }
+
+    fn git_ignored(&mut self, dir_entries: &[std::fs::DirEntry]) -> std::collections::HashSet<PathBuf> {
+        let Some(checker) = self.ignore_checker.as_mut() else {
+            return std::collections::HashSet::new();
+        };
+        let paths: Vec<PathBuf> = dir_entries.iter().map(|entry| entry.path()).collect();
+        match checker.ignored(&paths) {
+            Some(ignored) => ignored,
+            None => {
+                // git exits on paths it refuses (e.g. beyond a symlink); start a
+                // fresh checker for the rest of the walk
+                self.ignore_checker = GitIgnoreChecker::spawn(self.base_path);
+                std::collections::HashSet::new()
+            }
+        }
+    }
+}
+
+fn list_dir_recursive(walk: &mut DirWalk, current_path: &Path, depth: u32) -> Result<Vec<FileEntry>, String> {
+    if depth == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+
+    for VisibleEntry { entry, name, relative_path } in walk.visible_entries(current_path)? {
+        let path = entry.path();
+        let is_directory = path.is_dir();
+
+        // Descend only into directories not seen yet (a symlink back to an ancestor
+        // would otherwise recurse until the depth limit)
+        let descend = is_directory
+            && depth > 1
+            && (walk.follow_symlinks || !entry.file_type().map(|kind| kind.is_symlink()).unwrap_or(false))
+            && std::fs::canonicalize(&path)
+                .map(|canonical| walk.visited.insert(canonical))
+                .unwrap_or(false);
+
+        let children = if descend {
+            Some(list_dir_recursive(walk, &path, depth - 1)?)
+        } else if is_directory {
+            Some(Vec::new()) // Empty children at the depth limit or for skipped directories
+        } else {
+            None
+        };
+        // Lazy trees render expand arrows from this, so it applies the same
+        // filters as the listing; a directory of only hidden or ignored entries has none
+        let has_children = match &children {
+            Some(children) if descend => !children.is_empty(),
+            Some(_) => walk.visible_entries(&path).map(|visible| !visible.is_empty()).unwrap_or(false),
+            None => false,
+        };
+
+        let metadata = if walk.include_metadata { entry.metadata().ok() } else { None };
+        let size = metadata.as_ref().filter(|_| !is_directory).map(|metadata| metadata.len());
+        let modified = metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_millis() as u64);
+
+        entries.push(FileEntry {
+            name,
+            path: relative_path,
+            is_directory,
+            children,
+            has_children,
+            git_status: None,
+            size,
+            modified,
+        });
+    }
+
+    sort_file_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Sort: directories first, then files, both alphabetically. Names differing
+/// only in case are ordered exactly too, so the order (and paging) is stable.
+fn sort_file_entries(entries: &mut [FileEntry]) {
+    entries.sort_by(|a, b| {
+        match (a.is_directory, b.is_directory) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.name.cmp(&b.name)),
+        }
+    });
+}
+
+/// Default and maximum number of `search_files` results
+const SEARCH_DEFAULT_LIMIT: usize = 50;
+const SEARCH_MAX_LIMIT: usize = 500;
+/// Files examined before `search_files` gives up on finding better matches
+const SEARCH_MAX_FILES: usize = 100_000;
+
+/// Fuzzy-find files in a repo by path (command-palette style). Skips hidden
+/// entries, `.git`, `node_modules` and `target` like the file tree, and returns
+/// the best `limit` matches, best first.
+#[tauri::command]
+pub async fn search_files(repo_path: String, query: String, limit: Option<usize>) -> Result<Vec<FileEntry>, String> {
+    // The walk is synchronous filesystem work; keep it off the async runtime
+    tokio::task::spawn_blocking(move || fuzzy_find_files(repo_path, query, limit))
+        .await
+        .map_err(|e| format!("File search failed: {}", e))?
+}
+
+fn fuzzy_find_files(repo_path: String, query: String, limit: Option<usize>) -> Result<Vec<FileEntry>, String> {
+    let root = PathBuf::from(&repo_path);
+    if !root.is_dir() {
+        return Err(format!("Directory does not exist: {}", repo_path));
+    }
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit.unwrap_or(SEARCH_DEFAULT_LIMIT).clamp(1, SEARCH_MAX_LIMIT);
+
+    let query_text: String = query.iter().collect();
+    let mut matches: Vec<(i64, String)> = Vec::new();
+    // Matches containing the query verbatim in the file name; once there are
+    // `limit` of them they fill the results with strong hits, so the walk stops early
+    let mut strong_matches = 0;
+    let mut files_seen = 0;
+    let mut pending_dirs = vec![root.clone()];
+
+    'walk: while let Some(dir) = pending_dirs.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name == "node_modules" || name == "target" {
+                continue;
+            }
+            // Symlinked directories aren't followed, so loops can't trap the walk
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending_dirs.push(entry.path());
+                continue;
+            }
+
+            files_seen += 1;
+            let path = entry.path();
+            let relative = path
+                .strip_prefix(&root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| name.clone());
+            if let Some(score) = fuzzy_path_score(&relative, &query) {
+                if name.to_lowercase().contains(&query_text) {
+                    strong_matches += 1;
+                }
+                matches.push((score, relative));
+            }
+            if strong_matches >= limit || files_seen >= SEARCH_MAX_FILES {
+                break 'walk;
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.len().cmp(&b.1.len())).then_with(|| a.1.cmp(&b.1)));
+    Ok(matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| FileEntry {
+            name: path.rsplit('/').next().unwrap_or(&path).to_string(),
+            path,
+            is_directory: false,
+            children: None,
+            has_children: false,
+            git_status: None,
+            size: None,
+            modified: None,
+        })
+        .collect())
+}
+
+/// A line matching a `search_in_files` query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub path: String,
+    pub line_number: u32,
+    pub line_text: String,
+}
+
+/// Default and maximum number of `search_in_files` hits
+const CONTENT_SEARCH_DEFAULT_RESULTS: usize = 200;
+const CONTENT_SEARCH_MAX_RESULTS: usize = 2000;
+/// Bytes read (git output or file contents) before a content search stops
+const CONTENT_SEARCH_MAX_BYTES: usize = 32 * 1024 * 1024;
+/// Matching lines longer than this are truncated, e.g. minified bundles
+const SEARCH_HIT_MAX_LINE_CHARS: usize = 500;
+
+/// Find lines containing `query` (a literal, or a Rust-syntax regex with
+/// `is_regex`) across a directory. Inside a work tree only files git doesn't
+/// ignore are searched, literals via `git grep`; elsewhere the tree is walked
+/// with the file tree's skips. Stops at `max_results` hits or after scanning
+/// `CONTENT_SEARCH_MAX_BYTES`.
+#[tauri::command]
+pub async fn search_in_files(
+    repo_path: String,
+    query: String,
+    is_regex: Option<bool>,
+    max_results: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    let root = PathBuf::from(&repo_path);
+    if !root.is_dir() {
+        return Err(format!("Directory does not exist: {}", repo_path));
+    }
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let is_regex = is_regex.unwrap_or(false);
+    let max_results = max_results
+        .unwrap_or(CONTENT_SEARCH_DEFAULT_RESULTS)
+        .clamp(1, CONTENT_SEARCH_MAX_RESULTS);
+
+    let pattern = if is_regex {
+        regex::Regex::new(&query).map_err(|e| format!("Invalid regex: {}", e))?
+    } else {
+        regex::Regex::new(&regex::escape(&query)).map_err(|e| format!("Invalid query: {}", e))?
+    };
+
+    let in_work_tree = is_inside_work_tree(&root).await;
+    if in_work_tree && !is_regex {
+        return git_grep(&repo_path, &query, max_results).await;
+    }
+
+    // Regexes are matched with the same engine that validated them, rather than
+    // git grep's PCRE, which differs in syntax and isn't built into every git
+    let files = if in_work_tree { Some(list_searchable_files(&repo_path).await?) } else { None };
+    tokio::task::spawn_blocking(move || match files {
+        Some(files) => grep_listed_files(&root, &files, &pattern, max_results),
+        None => walk_grep(&root, &pattern, max_results),
+    })
+    .await
+    .map_err(|e| format!("Content search failed: {}", e))
+}
+
+/// Tracked and untracked, non-ignored files under `repo_path`, relative to it
+async fn list_searchable_files(repo_path: &str) -> Result<Vec<String>, String> {
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "ls-files", "-z", "--cached", "--others", "--exclude-standard"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to list files: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to list files: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_string())
+        .collect();
+    // Conflicted files are listed once per index stage
+    files.dedup();
+    Ok(files)
+}
+
+async fn git_grep(repo_path: &str, query: &str, max_results: usize) -> Result<Vec<SearchHit>, String> {
+    use tokio::io::AsyncBufReadExt;
+
+    // -z separates path, line number and text with NULs so any path parses;
+    // --untracked includes new files an agent just wrote
+    let mut child = AsyncCommand::new("git")
+        .args(["-C", repo_path, "grep", "-n", "-z", "-I", "--no-color", "--untracked", "--fixed-strings", "-e", query])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run git grep: {}", e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to run git grep: stdout not captured")?;
+    let mut reader = tokio::io::BufReader::new(stdout);
+    let mut hits = Vec::new();
+    let mut bytes_read = 0;
+    let mut line = Vec::new();
+    while hits.len() < max_results && bytes_read < CONTENT_SEARCH_MAX_BYTES {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| format!("Failed to read git grep output: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        bytes_read += read;
+        if let Some(hit) = parse_git_grep_line(&line) {
+            hits.push(hit);
+        }
+    }
+
+    if hits.len() >= max_results || bytes_read >= CONTENT_SEARCH_MAX_BYTES {
+        // Enough results; don't wait for git to finish the whole repo
+        let _ = child.kill().await;
+        return Ok(hits);
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run git grep: {}", e))?;
+    // Exit code 1 just means no matches
+    if !output.status.success() && output.status.code() != Some(1) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git grep failed: {}", stderr.trim()));
+    }
+    Ok(hits)
+}
+
+/// Parse one `git grep -n -z` record: `path\0line\0text\n`
+fn parse_git_grep_line(record: &[u8]) -> Option<SearchHit> {
+    let record = String::from_utf8_lossy(record);
+    let mut fields = record.trim_end_matches(['\n', '\r']).splitn(3, '\0');
+    let path = fields.next()?.to_string();
+    let line_number = fields.next()?.parse().ok()?;
+    let line_text = truncate_hit_line(fields.next()?);
+    Some(SearchHit { path, line_number, line_text })
+}
+
+fn truncate_hit_line(line: &str) -> String {
+    match line.char_indices().nth(SEARCH_HIT_MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+/// Content search over files listed by git, relative to `root`
+fn grep_listed_files(root: &Path, files: &[String], pattern: &regex::Regex, max_results: usize) -> Vec<SearchHit> {
+    let mut hits = Vec::new();
+    let mut bytes_scanned = 0;
+    for relative in files {
+        if grep_file(&root.join(relative), relative, pattern, max_results, &mut hits, &mut bytes_scanned) {
+            break;
+        }
+    }
+    hits
+}
+
+/// Content search for directories outside a git work tree
+fn walk_grep(root: &Path, pattern: &regex::Regex, max_results: usize) -> Vec<SearchHit> {
+    let mut hits = Vec::new();
+    let mut bytes_scanned = 0;
+    let mut pending_dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending_dirs.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = read_dir.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name == "node_modules" || name == "target" {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending_dirs.push(entry.path());
+                continue;
+            }
+
+            let path = entry.path();
+            let relative = path
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or(name);
+            if grep_file(&path, &relative, pattern, max_results, &mut hits, &mut bytes_scanned) {
+                return hits;
+            }
+        }
+    }
+
+    hits
+}
+
+/// Append `path`'s matching lines to `hits`. Files that would push the scan past
+/// `CONTENT_SEARCH_MAX_BYTES` are skipped without being read. Returns true once
+/// the search should stop.
+fn grep_file(
+    path: &Path,
+    relative: &str,
+    pattern: &regex::Regex,
+    max_results: usize,
+    hits: &mut Vec<SearchHit>,
+    bytes_scanned: &mut usize,
+) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    let size = usize::try_from(metadata.len()).unwrap_or(usize::MAX);
+    if !metadata.is_file() || size > CONTENT_SEARCH_MAX_BYTES - *bytes_scanned {
+        return false;
+    }
+    let Ok(bytes) = std::fs::read(path) else {
+        return false;
+    };
+    *bytes_scanned += bytes.len().min(CONTENT_SEARCH_MAX_BYTES - *bytes_scanned);
+    if looks_binary(&bytes) {
+        return false;
+    }
+
+    for (index, line) in String::from_utf8_lossy(&bytes).lines().enumerate() {
+        if pattern.is_match(line) {
+            hits.push(SearchHit {
+                path: relative.to_string(),
+                line_number: index as u32 + 1,
+                line_text: truncate_hit_line(line),
+            });
+            if hits.len() >= max_results {
+                return true;
+            }
+        }
+    }
+    *bytes_scanned >= CONTENT_SEARCH_MAX_BYTES
+}
+
+/// Score `path` against a lowercase `query` whose characters must all appear
+/// in order. Matching within the file name alone beats matching across
+/// directories; consecutive runs and word starts score higher, and longer
+/// paths slightly lower. None when it doesn't match.
+pub(crate) fn fuzzy_path_score(path: &str, query: &[char]) -> Option<i64> {
+    let lower: Vec<char> = path.to_lowercase().chars().collect();
+    let name_start = lower.iter().rposition(|c| *c == '/').map_or(0, |index| index + 1);
+
+    let length_penalty = lower.len() as i64;
+    if let Some(score) = subsequence_score(&lower[name_start..], query) {
+        return Some((score + 100) * 10 - length_penalty);
+    }
+    subsequence_score(&lower, query).map(|score| score * 10 - length_penalty)
+}
+
+/// Greedy in-order match of `query` in `text`, rewarding consecutive characters
+/// and word starts
+fn subsequence_score(text: &[char], query: &[char]) -> Option<i64> {
+    let mut score: i64 = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for &wanted in query {
+        let index = (position..text.len()).find(|&index| text[index] == wanted)?;
+        score += 1;
+        if index > 0 && previous == Some(index - 1) {
+            score += 5;
+        }
+        if index == 0 || matches!(text[index - 1], '/' | '_' | '-' | '.' | ' ') {
+            score += 3;
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+async fn is_inside_work_tree(path: &Path) -> bool {
+    AsyncCommand::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .output()
+        .await
+        .map(|output| output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
+        .unwrap_or(false)
+}
+
+/// A long-running `git check-ignore --stdin` shared by a whole directory walk,
+/// so listing a tree spawns git once rather than once per directory. Honors
+/// nested .gitignore files, .git/info/exclude and the global excludes file.
+struct GitIgnoreChecker {
+    child: std::process::Child,
+    requests: std::sync::mpsc::Sender<Vec<u8>>,
+    responses: std::io::BufReader<std::process::ChildStdout>,
 }
 
-/// List all files in a directory recursively
-#[tauri::command]
-pub async fn list_directory_files(dir_path: String, max_depth: Option<u32>, show_hidden: Option<bool>) -> Result<Vec<FileEntry>, String> {
-    let path = PathBuf::from(&dir_path);
-    if !path.exists() {
-        return Err(format!("Directory does not exist: {}", dir_path));
+impl GitIgnoreChecker {
+    fn spawn(dir: &Path) -> Option<Self> {
+        use std::io::Write;
+
+        let mut child = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["check-ignore", "--stdin", "-z", "--verbose", "--non-matching"])
+            // Answer each path as it's read instead of buffering until stdin closes
+            .env("GIT_FLUSH", "1")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut stdin = child.stdin.take()?;
+        let responses = std::io::BufReader::new(child.stdout.take()?);
+
+        // Feed stdin from a separate thread so a full stdout pipe can't deadlock us
+        let (requests, pending) = std::sync::mpsc::channel::<Vec<u8>>();
+        std::thread::spawn(move || {
+            for batch in pending {
+                if stdin.write_all(&batch).and_then(|_| stdin.flush()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Some(Self { child, requests, responses })
     }
 
-    let depth = max_depth.unwrap_or(10);
-    let include_hidden = show_hidden.unwrap_or(false);
-    list_dir_recursive(&path, &path, depth, include_hidden)
+    /// Which of `paths` are ignored, or None if git exited mid-batch
+    fn ignored(&mut self, paths: &[PathBuf]) -> Option<std::collections::HashSet<PathBuf>> {
+        use std::io::BufRead;
+
+        let mut ignored = std::collections::HashSet::new();
+        if paths.is_empty() {
+            return Some(ignored);
+        }
+
+        let mut batch = Vec::new();
+        for path in paths {
+            batch.extend_from_slice(path.to_string_lossy().as_bytes());
+            batch.push(0);
+        }
+        self.requests.send(batch).ok()?;
+
+        // Answers come back in request order as source, line, pattern and path.
+        // Non-matching paths have an empty source; a `!` pattern re-includes the path.
+        let mut fields: [Vec<u8>; 4] = Default::default();
+        for path in paths {
+            for field in fields.iter_mut() {
+                field.clear();
+                if self.responses.read_until(0, field).ok()? == 0 {
+                    return None;
+                }
+            }
+            let matched = fields[0].len() > 1;
+            if matched && !fields[2].starts_with(b"!") {
+                ignored.insert(path.clone());
+            }
+        }
+        Some(ignored)
+    }
 }
 
-fn list_dir_recursive(base_path: &Path, current_path: &Path, depth: u32, show_hidden: bool) -> Result<Vec<FileEntry>, String> {
-    if depth == 0 {
-        return Ok(Vec::new());
+impl Drop for GitIgnoreChecker {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
     }
+}
 
-    let mut entries = Vec::new();
+/// Annotate file tree entries with their git status using a single `git status` call.
+/// `repo_path` may be a subdirectory of the work tree; entry paths are relative to it.
+/// Deleted files no longer exist on disk, so they are added to the tree as entries.
+pub async fn annotate_file_tree_status(repo_path: &str, entries: &mut Vec<FileEntry>) -> Result<(), String> {
+    // Porcelain paths are relative to the work tree root, not to `repo_path`
+    let prefix_output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--show-prefix"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to get status: {}", e))?;
+    if !prefix_output.status.success() {
+        let stderr = String::from_utf8_lossy(&prefix_output.stderr);
+        return Err(format!("Failed to get status: {}", stderr));
+    }
+    let prefix = String::from_utf8_lossy(&prefix_output.stdout).trim_end_matches('\n').to_string();
 
-    let read_dir = std::fs::read_dir(current_path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
+    let output = AsyncCommand::new("git")
+        .args(["-C", repo_path, "status", "--porcelain", "-z", "--untracked-files=all", "--", "."])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to get status: {}", e))?;
 
-    for entry in read_dir {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
-        let name = entry.file_name().to_string_lossy().to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to get status: {}", stderr));
+    }
+
+    let statuses: std::collections::HashMap<String, String> = parse_file_tree_statuses(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter_map(|(path, status)| Some((path.strip_prefix(&prefix)?.to_string(), status)))
+        .collect();
+    insert_deleted_entries(entries, &statuses);
+    apply_file_tree_statuses(entries, &statuses);
+    Ok(())
+}
 
-        // Skip hidden files unless show_hidden is true
-        if !show_hidden && name.starts_with('.') {
+/// Add entries for deleted files (and their vanished parent directories) so the
+/// tree can show them. Hidden paths are skipped, as in the listing itself.
+fn insert_deleted_entries(entries: &mut Vec<FileEntry>, statuses: &std::collections::HashMap<String, String>) {
+    for (path, status) in statuses {
+        if status != "deleted" || path.split('/').any(|part| part.starts_with('.')) {
             continue;
         }
 
-        // Always skip these large directories
-        if name == "node_modules" || name == "target" || name == ".git" {
-            continue;
+        let mut level = &mut *entries;
+        let mut parent = String::new();
+        let mut parts = path.split('/').peekable();
+        while let Some(name) = parts.next() {
+            let entry_path = if parent.is_empty() { name.to_string() } else { format!("{}/{}", parent, name) };
+            let is_directory = parts.peek().is_some();
+            let index = match level.iter().position(|entry| entry.name == name) {
+                Some(index) => index,
+                None => {
+                    level.push(FileEntry {
+                        name: name.to_string(),
+                        path: entry_path.clone(),
+                        is_directory,
+                        children: is_directory.then(Vec::new),
+                        has_children: is_directory,
+                        git_status: None,
+                        size: None,
+                        modified: None,
+                    });
+                    sort_file_entries(level);
+                    level.iter().position(|entry| entry.name == name).unwrap_or_default()
+                }
+            };
+            if !is_directory {
+                break;
+            }
+            let entry = &mut level[index];
+            entry.has_children = true;
+            level = entry.children.get_or_insert_with(Vec::new);
+            parent = entry_path;
         }
+    }
+}
 
-        let relative_path = path.strip_prefix(base_path)
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|_| name.clone());
+/// Parse `git status --porcelain -z` into path -> tree status
+fn parse_file_tree_statuses(output: &str) -> std::collections::HashMap<String, String> {
+    let mut statuses = std::collections::HashMap::new();
+    let mut records = output.split('\0');
 
-        let is_directory = path.is_dir();
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+        let index_status = record.chars().next().unwrap_or(' ');
+        let worktree_status = record.chars().nth(1).unwrap_or(' ');
+        let file = record[3..].to_string();
 
-        let children = if is_directory && depth > 1 {
-            Some(list_dir_recursive(base_path, &path, depth - 1, show_hidden)?)
-        } else if is_directory {
-            Some(Vec::new()) // Empty children if we've hit depth limit
-        } else {
-            None
-        };
+        // Renames and copies are followed by the original path
+        if index_status == 'R' || index_status == 'C' {
+            records.next();
+        }
 
-        entries.push(FileEntry {
-            name,
-            path: relative_path,
-            is_directory,
-            children,
-        });
+        let status = match (index_status, worktree_status) {
+            ('?', '?') => "untracked",
+            ('D', _) | (_, 'D') => "deleted",
+            (' ', _) => "modified",
+            _ => "staged",
+        };
+        statuses.insert(file, status.to_string());
     }
 
-    // Sort: directories first, then files, both alphabetically
-    entries.sort_by(|a, b| {
-        match (a.is_directory, b.is_directory) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        }
-    });
+    statuses
+}
 
-    Ok(entries)
+fn apply_file_tree_statuses(entries: &mut [FileEntry], statuses: &std::collections::HashMap<String, String>) {
+    for entry in entries.iter_mut() {
+        if entry.is_directory {
+            let prefix = format!("{}/", entry.path);
+            if statuses.keys().any(|path| path.starts_with(&prefix)) {
+                entry.git_status = Some("has_changes".to_string());
+            }
+            if let Some(children) = entry.children.as_mut() {
+                apply_file_tree_statuses(children, statuses);
+            }
+        } else {
+            entry.git_status = statuses.get(&entry.path).cloned();
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -794,11 +3075,23 @@ pub struct FileContent {
     pub content: String,
     pub language: String,
     pub size: u64,
+    pub is_binary: bool,
+    pub total_lines: usize,
 }
 
-/// Read the contents of a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryFileContent {
+    pub path: String,
+    pub content_base64: String,
+    pub mime_type: String,
+    pub size: u64,
+}
+
+const MAX_READ_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Read the contents of a file, optionally only lines `start_line..=end_line` (1-based)
 #[tauri::command]
-pub async fn read_file(file_path: String) -> Result<FileContent, String> {
+pub async fn read_file(file_path: String, start_line: Option<u32>, end_line: Option<u32>) -> Result<FileContent, String> {
     let path = PathBuf::from(&file_path);
 
     if !path.exists() {
@@ -815,14 +3108,24 @@ pub async fn read_file(file_path: String) -> Result<FileContent, String> {
 
     let size = metadata.len();
 
-    // Don't read files larger than 5MB
-    if size > 5 * 1024 * 1024 {
-        return Err("File is too large to read (max 5MB)".to_string());
-    }
+    let (bytes, total_lines) = if start_line.is_some() || end_line.is_some() {
+        // Ranged reads stream the file, so the 5MB cap applies to the slice only
+        read_line_range(&path, start_line.unwrap_or(1), end_line.unwrap_or(u32::MAX))?
+    } else {
+        // Don't read files larger than 5MB
+        if size > MAX_READ_BYTES {
+            return Err("File is too large to read (max 5MB)".to_string());
+        }
+        let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let total_lines = count_lines(&bytes);
+        (bytes, total_lines)
+    };
 
-    // Read file contents
-    let content = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+    // Non-UTF-8 files are reported as binary instead of failing
+    let (content, is_binary) = match String::from_utf8(bytes) {
+        Ok(content) if !content.contains('\0') => (content, false),
+        _ => (String::new(), true),
+    };
 
     // Determine language from extension
     let language = path.extension()
@@ -859,9 +3162,146 @@ pub async fn read_file(file_path: String) -> Result<FileContent, String> {
         content,
         language,
         size,
+        is_binary,
+        total_lines,
+    })
+}
+
+/// Read lines `start..=end` (1-based) and count the file's lines in one
+/// streaming pass. Only the requested slice is buffered, so a huge line outside
+/// it (or past the 5MB cap inside it) is never held in memory.
+fn read_line_range(path: &Path, start: u32, end: u32) -> Result<(Vec<u8>, usize), String> {
+    use std::io::Read;
+
+    if start == 0 || end < start {
+        return Err(format!("Invalid line range: {}-{}", start, end));
+    }
+
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut buffer = [0u8; 64 * 1024];
+    let mut slice = Vec::new();
+    // Line the next byte belongs to
+    let mut line_number: u32 = 1;
+    let mut newlines = 0;
+    let mut last_byte = b'\n';
+
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+
+        let mut rest = &buffer[..read];
+        while !rest.is_empty() {
+            let segment = match rest.iter().position(|&byte| byte == b'\n') {
+                Some(index) => &rest[..=index],
+                None => rest,
+            };
+            if (start..=end).contains(&line_number) {
+                if (slice.len() + segment.len()) as u64 > MAX_READ_BYTES {
+                    return Err("Requested line range is too large to read (max 5MB)".to_string());
+                }
+                slice.extend_from_slice(segment);
+            }
+            if segment.ends_with(b"\n") {
+                newlines += 1;
+                line_number = line_number.saturating_add(1);
+            }
+            rest = &rest[segment.len()..];
+        }
+        last_byte = buffer[read - 1];
+    }
+
+    let total_lines = if last_byte == b'\n' { newlines } else { newlines + 1 };
+    Ok((slice, total_lines))
+}
+
+/// Count lines; a trailing line without a newline still counts
+fn count_lines(bytes: &[u8]) -> usize {
+    let newlines = bytes.iter().filter(|&&byte| byte == b'\n').count();
+    match bytes.last() {
+        Some(&byte) if byte != b'\n' => newlines + 1,
+        _ => newlines,
+    }
+}
+
+/// Read a file as base64, for images and other binary content
+#[tauri::command]
+pub async fn read_file_binary(file_path: String) -> Result<BinaryFileContent, String> {
+    use base64::Engine;
+
+    let path = PathBuf::from(&file_path);
+
+    if !path.exists() {
+        return Err(format!("File does not exist: {}", file_path));
+    }
+
+    if path.is_dir() {
+        return Err("Cannot read a directory".to_string());
+    }
+
+    let metadata = std::fs::metadata(&path)
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+
+    let size = metadata.len();
+
+    // Same 5MB cap as read_file
+    if size > MAX_READ_BYTES {
+        return Err("File is too large to read (max 5MB)".to_string());
+    }
+
+    let bytes = std::fs::read(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    Ok(BinaryFileContent {
+        mime_type: detect_mime_type(&path, &bytes).to_string(),
+        content_base64: base64::engine::general_purpose::STANDARD.encode(&bytes),
+        path: file_path,
+        size,
     })
 }
 
+/// Detect a MIME type from magic bytes, falling back to the file extension
+fn detect_mime_type(path: &Path, bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return "image/png";
+    }
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return "image/jpeg";
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return "image/gif";
+    }
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if bytes.starts_with(b"%PDF-") {
+        return "application/pdf";
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return "application/zip";
+    }
+
+    match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("bmp") => "image/bmp",
+        Some("pdf") => "application/pdf",
+        Some("zip") => "application/zip",
+        Some("wasm") => "application/wasm",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("ttf") => "font/ttf",
+        Some("mp3") => "audio/mpeg",
+        Some("mp4") => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileDiff {
     pub path: String,
@@ -870,11 +3310,29 @@ pub struct FileDiff {
     pub language: String,
     pub is_new_file: bool,
     pub is_deleted: bool,
+    /// Either version looks binary; contents are left empty and only sizes are set
+    pub is_binary: bool,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+/// How many leading bytes are checked for NUL when detecting binary content
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// Same heuristic git uses: a NUL byte near the start means binary
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_BYTES).any(|&b| b == 0)
 }
 
-/// Get diff for a specific file (shows old vs new content)
+/// Get diff for a specific file (shows old vs new content). Compares HEAD with
+/// the working tree unless `base_ref`/`target_ref` are given.
 #[tauri::command]
-pub async fn git_file_diff(repo_path: String, file_path: String) -> Result<FileDiff, String> {
+pub async fn git_file_diff(
+    repo_path: String,
+    file_path: String,
+    base_ref: Option<String>,
+    target_ref: Option<String>,
+) -> Result<FileDiff, String> {
     let repo = PathBuf::from(&repo_path);
 
     if !repo.exists() {
@@ -922,92 +3380,351 @@ pub async fn git_file_diff(repo_path: String, file_path: String) -> Result<FileD
         .unwrap_or("plaintext")
         .to_string();
 
-    // Check if file is untracked (new file)
+    let (old_bytes, new_bytes, is_new_file, is_deleted) = match resolve_diff_range(&repo_path, base_ref, target_ref).await? {
+        Some(range) => {
+            let old_bytes = show_blob(&repo_path, &range.base, &relative_path).await;
+            let new_bytes = match &range.target {
+                Some(target) => show_blob(&repo_path, target, &relative_path).await,
+                None => std::fs::read(&full_file_path).ok(),
+            };
+            let is_new_file = old_bytes.is_none();
+            let is_deleted = new_bytes.is_none();
+            (old_bytes.unwrap_or_default(), new_bytes.unwrap_or_default(), is_new_file, is_deleted)
+        }
+        None => {
+            // Check if file is untracked (new file)
+            let status_output = AsyncCommand::new("git")
+                .args(["-C", repo.to_str().unwrap(), "status", "--porcelain", &relative_path])
+                .output()
+                .await
+                .map_err(|e| format!("Failed to get git status: {}", e))?;
+
+            let status_str = String::from_utf8_lossy(&status_output.stdout);
+            let is_new_file = status_str.starts_with("??") || status_str.starts_with("A ");
+            let is_deleted = status_str.starts_with(" D") || status_str.starts_with("D ");
+
+            // Get old bytes (from HEAD)
+            let old_bytes = if is_new_file {
+                Vec::new()
+            } else {
+                let output = AsyncCommand::new("git")
+                    .args(["-C", repo.to_str().unwrap(), "show", &format!("HEAD:{}", relative_path)])
+                    .output()
+                    .await;
+
+                match output {
+                    Ok(out) if out.status.success() => out.stdout,
+                    _ => Vec::new()
+                }
+            };
+
+            // Get new bytes (current working directory)
+            let new_bytes = if is_deleted {
+                Vec::new()
+            } else if full_file_path.exists() {
+                std::fs::read(&full_file_path)
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            (old_bytes, new_bytes, is_new_file, is_deleted)
+        }
+    };
+
+    let is_binary = looks_binary(&old_bytes) || looks_binary(&new_bytes);
+    let (old_content, new_content) = if is_binary {
+        (String::new(), String::new())
+    } else {
+        (
+            String::from_utf8_lossy(&old_bytes).to_string(),
+            String::from_utf8_lossy(&new_bytes).to_string(),
+        )
+    };
+
+    Ok(FileDiff {
+        path: relative_path,
+        old_content,
+        new_content,
+        language,
+        is_new_file,
+        is_deleted,
+        is_binary,
+        old_size: old_bytes.len() as u64,
+        new_size: new_bytes.len() as u64,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: String, // "context", "add", "delete"
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Parse a `@@ -a,b +c,d @@` range pair; an omitted count means 1
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let ranges = line.strip_prefix("@@ -")?;
+    let (ranges, _) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+
+    let parse_range = |range: &str| -> Option<(u32, u32)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    let (old_start, old_lines) = parse_range(old)?;
+    let (new_start, new_lines) = parse_range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Parse `git diff` output into hunks. File headers and binary notices are skipped.
+pub(crate) fn parse_diff_hunks(output: &str) -> Vec<DiffHunk> {
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+
+    for line in output.lines() {
+        if line.starts_with("@@ ") {
+            if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+                hunks.push(DiffHunk { old_start, old_lines, new_start, new_lines, lines: Vec::new() });
+            }
+            continue;
+        }
+
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+
+        let (kind, content) = match line.chars().next() {
+            Some(' ') => ("context", &line[1..]),
+            Some('+') => ("add", &line[1..]),
+            Some('-') => ("delete", &line[1..]),
+            // "\ No newline at end of file"
+            Some('\\') => continue,
+            // An empty context line can lose its leading space
+            None => ("context", ""),
+            _ => continue,
+        };
+        hunk.lines.push(DiffLine { kind: kind.to_string(), content: content.to_string() });
+    }
+
+    hunks
+}
+
+/// Get the changes to a single file against HEAD as structured hunks, so the
+/// UI can show a compact patch without loading both full versions
+#[tauri::command]
+pub async fn git_file_hunks(repo_path: String, file_path: String) -> Result<Vec<DiffHunk>, String> {
+    let repo = PathBuf::from(&repo_path);
+
+    if !repo.exists() {
+        return Err("Repository path does not exist".to_string());
+    }
+
+    let full_file_path = if file_path.starts_with(&repo_path) {
+        PathBuf::from(&file_path)
+    } else {
+        repo.join(&file_path)
+    };
+
+    let relative_path = full_file_path.strip_prefix(&repo)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or(file_path.clone());
+
     let status_output = AsyncCommand::new("git")
-        .args(["-C", repo.to_str().unwrap(), "status", "--porcelain", &relative_path])
+        .args(["-C", &repo_path, "status", "--porcelain", "--", &relative_path])
         .output()
         .await
         .map_err(|e| format!("Failed to get git status: {}", e))?;
+    let is_untracked = String::from_utf8_lossy(&status_output.stdout).starts_with("??");
 
-    let status_str = String::from_utf8_lossy(&status_output.stdout);
-    let is_new_file = status_str.starts_with("??") || status_str.starts_with("A ");
-    let is_deleted = status_str.starts_with(" D") || status_str.starts_with("D ");
-
-    // Get old content (from HEAD)
-    let old_content = if is_new_file {
-        String::new()
+    // Untracked files aren't known to `git diff HEAD`, so diff them against an empty file
+    let output = if is_untracked {
+        let null_path = if cfg!(windows) { "NUL" } else { "/dev/null" };
+        AsyncCommand::new("git")
+            .args(["-C", &repo_path, "diff", "--no-color", "--unified=3", "--no-index", "--", null_path, &relative_path])
+            .output()
+            .await
     } else {
-        let output = AsyncCommand::new("git")
-            .args(["-C", repo.to_str().unwrap(), "show", &format!("HEAD:{}", relative_path)])
+        AsyncCommand::new("git")
+            .args(["-C", &repo_path, "diff", "--no-color", "--unified=3", "HEAD", "--", &relative_path])
             .output()
-            .await;
+            .await
+    }
+    .map_err(|e| format!("Failed to get file diff: {}", e))?;
+
+    // `--no-index` exits with 1 when the files differ
+    let succeeded = output.status.success() || (is_untracked && output.status.code() == Some(1));
+    if !succeeded {
+        return Err(format!("Failed to get file diff: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(parse_diff_hunks(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Helper functions
+
+/// Resolve the repo's default branch. Tries `origin/HEAD`, then asks the remote
+/// (cached as `origin/HEAD` so later calls stay offline), then falls back to
+/// guesses that are never cached: `origin/main` / `origin/master`, the branch
+/// checked out in the main working tree, `init.defaultBranch`, then "main".
+pub(crate) async fn get_default_branch(repo_path: &Path) -> Result<String, String> {
+    let repo = repo_path.to_string_lossy().to_string();
 
-        match output {
-            Ok(out) if out.status.success() => {
-                String::from_utf8_lossy(&out.stdout).to_string()
+    if let Some(ref_str) = git_stdout(&["-C", &repo, "symbolic-ref", "refs/remotes/origin/HEAD"]).await {
+        if let Some(branch) = ref_str.strip_prefix("refs/remotes/origin/") {
+            return Ok(branch.to_string());
+        }
+    }
+
+    let has_remotes = !git_stdout(&["-C", &repo, "remote"]).await.unwrap_or_default().is_empty();
+    if has_remotes {
+        if let Some(branch) = remote_default_branch(&repo).await {
+            if remote_branch_exists(&repo, &branch).await {
+                cache_default_branch(&repo, &branch).await;
             }
-            _ => String::new()
+            return Ok(branch);
         }
-    };
+    }
+
+    // Offline from here on, so these are guesses and aren't persisted
+    for branch in ["main", "master"] {
+        if remote_branch_exists(&repo, branch).await {
+            return Ok(branch.to_string());
+        }
+    }
+
+    // A nonstandard default: the main working tree usually sits on it, even
+    // when `repo_path` is one of its worktrees
+    if let Some(common_dir) = git_stdout(&["-C", &repo, "rev-parse", "--path-format=absolute", "--git-common-dir"]).await {
+        if let Some(branch) = git_stdout(&["--git-dir", &common_dir, "symbolic-ref", "--short", "HEAD"]).await {
+            // Without a remote there's no remote default to disagree with
+            if !has_remotes || remote_branch_exists(&repo, &branch).await {
+                return Ok(branch);
+            }
+        }
+    }
+
+    if let Some(branch) = git_stdout(&["-C", &repo, "config", "--get", "init.defaultBranch"]).await {
+        return Ok(branch);
+    }
+
+    Ok("main".to_string())
+}
+
+/// Trimmed stdout of a git command, or `None` if it failed or printed nothing
+async fn git_stdout(args: &[&str]) -> Option<String> {
+    let output = AsyncCommand::new("git").args(args).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+async fn remote_branch_exists(repo_path: &str, branch: &str) -> bool {
+    git_stdout(&["-C", repo_path, "rev-parse", "--verify", "--quiet", &format!("refs/remotes/origin/{}", branch)])
+        .await
+        .is_some()
+}
 
-    // Get new content (current working directory)
-    let new_content = if is_deleted {
-        String::new()
-    } else if full_file_path.exists() {
-        std::fs::read_to_string(&full_file_path)
-            .unwrap_or_default()
-    } else {
-        String::new()
-    };
+/// Longest we wait for `origin` to report its default branch
+const REMOTE_HEAD_TIMEOUT_SECS: u64 = 5;
+
+/// The branch `origin`'s HEAD points at, via `ls-remote --symref`. `None` when
+/// the remote is unreachable, needs credentials or doesn't answer in time.
+async fn remote_default_branch(repo_path: &str) -> Option<String> {
+    let lookup = AsyncCommand::new("git")
+        .args(["-C", repo_path, "ls-remote", "--symref", "origin", "HEAD"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_SSH_COMMAND", batch_ssh_command(None))
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(std::time::Duration::from_secs(REMOTE_HEAD_TIMEOUT_SECS), lookup)
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_symref_head(&String::from_utf8_lossy(&output.stdout))
+}
 
-    Ok(FileDiff {
-        path: relative_path,
-        old_content,
-        new_content,
-        language,
-        is_new_file,
-        is_deleted,
+/// Branch from the `ref: refs/heads/<branch>` line for HEAD in `ls-remote --symref` output
+fn parse_symref_head(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (target, name) = line.strip_prefix("ref: ")?.split_once('\t')?;
+        (name.trim() == "HEAD").then_some(())?;
+        target.strip_prefix("refs/heads/").map(str::to_string)
     })
 }
 
-// Helper functions
-
-async fn get_default_branch(repo_path: &Path) -> Result<String, String> {
-    // Try to get from remote HEAD
-    let output = AsyncCommand::new("git")
-        .args(["-C", repo_path.to_str().unwrap(), "symbolic-ref", "refs/remotes/origin/HEAD"])
+/// Point `origin/HEAD` at `origin/<branch>` so the next lookup is one command
+async fn cache_default_branch(repo_path: &str, branch: &str) {
+    let target = format!("refs/remotes/origin/{}", branch);
+    let _ = AsyncCommand::new("git")
+        .args(["-C", repo_path, "symbolic-ref", "refs/remotes/origin/HEAD", &target])
         .output()
         .await;
+}
 
-    if let Ok(output) = output {
-        if output.status.success() {
-            let ref_str = String::from_utf8_lossy(&output.stdout);
-            if let Some(branch) = ref_str.trim().strip_prefix("refs/remotes/origin/") {
-                return Ok(branch.to_string());
-            }
-        }
-    }
-
-    // Fall back to checking if main or master exists
-    for branch in &["main", "master"] {
-        let output = AsyncCommand::new("git")
-            .args(["-C", repo_path.to_str().unwrap(), "rev-parse", "--verify", &format!("origin/{}", branch)])
-            .output()
-            .await;
+async fn is_shallow_repository(repo_path: &str) -> bool {
+    AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--is-shallow-repository"])
+        .output()
+        .await
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "true")
+        .unwrap_or(false)
+}
 
-        if let Ok(output) = output {
-            if output.status.success() {
-                return Ok(branch.to_string());
-            }
+/// Make sure `origin/<branch>` resolves, fetching just that branch's tip if needed
+async fn ensure_remote_branch(repo_path: &str, branch: &str) {
+    let remote_ref = format!("origin/{}", branch);
+    let exists = AsyncCommand::new("git")
+        .args(["-C", repo_path, "rev-parse", "--verify", "--quiet", &remote_ref])
+        .output()
+        .await
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    if !exists {
+        let refspec = format!("+refs/heads/{0}:refs/remotes/origin/{0}", branch);
+        let mut args = vec!["-C", repo_path, "fetch"];
+        if is_shallow_repository(repo_path).await {
+            args.extend(["--depth", "1"]);
         }
+        args.extend(["origin", refspec.as_str()]);
+        let _ = AsyncCommand::new("git").args(&args).output().await;
     }
+}
 
-    Ok("main".to_string())
+/// The branch's configured upstream (which may be another remote or another
+/// name, e.g. `upstream/main` in a fork), else `origin/<branch>` if it exists
+async fn resolve_upstream(repo_path: &str, branch: &str) -> Option<String> {
+    if branch.is_empty() {
+        return None;
+    }
+    let configured = format!("{}@{{upstream}}", branch);
+    if let Some(upstream) = git_stdout(&["-C", repo_path, "rev-parse", "--abbrev-ref", &configured]).await {
+        return Some(upstream);
+    }
+    remote_branch_exists(repo_path, branch)
+        .await
+        .then(|| format!("origin/{}", branch))
 }
 
-async fn get_ahead_behind(repo_path: &str, branch: &str) -> Result<(u32, u32), String> {
+async fn get_ahead_behind(repo_path: &str, branch: &str, upstream: &str) -> Result<(u32, u32), String> {
     let output = AsyncCommand::new("git")
-        .args(["-C", repo_path, "rev-list", "--left-right", "--count", &format!("{}...origin/{}", branch, branch)])
+        .args(["-C", repo_path, "rev-list", "--left-right", "--count", &format!("{}...{}", branch, upstream)])
         .output()
         .await
         .map_err(|e| format!("Failed to get ahead/behind: {}", e))?;
@@ -1028,22 +3745,83 @@ async fn get_ahead_behind(repo_path: &str, branch: &str) -> Result<(u32, u32), S
     Ok((0, 0))
 }
 
-fn parse_repo_full_name(url: &str) -> Result<String, String> {
-    // Handle various URL formats:
+/// `git@host:owner/repo` scp-style and `ssh://` URLs
+fn is_ssh_url(url: &str) -> bool {
+    let url = url.trim();
+    if url.starts_with("ssh://") || url.starts_with("git+ssh://") {
+        return true;
+    }
+    // scp-style: user@host:path, with no scheme
+    !url.contains("://")
+        && url
+            .split_once(':')
+            .map(|(user_host, _)| user_host.contains('@') && !user_host.contains('/'))
+            .unwrap_or(false)
+}
+
+fn ssh_host(url: &str) -> Option<&str> {
+    let rest = url
+        .trim()
+        .strip_prefix("ssh://")
+        .or_else(|| url.trim().strip_prefix("git+ssh://"))
+        .unwrap_or(url.trim());
+    let host_part = rest.split(['/', ':']).next()?;
+    Some(host_part.rsplit('@').next().unwrap_or(host_part))
+}
+
+/// Hosting provider a clone URL points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitProvider {
+    GitHub,
+    GitLab,
+    Other,
+}
+
+impl GitProvider {
+    fn detect(url: &str) -> Self {
+        let url = url.trim();
+        let is_host = |host: &str| {
+            url.starts_with(&format!("https://{}/", host)) || url.starts_with(&format!("git@{}:", host))
+        };
+
+        if is_host(GITLAB_HOST) {
+            GitProvider::GitLab
+        } else if github_host().map(|host| is_host(&host)).unwrap_or(false) {
+            GitProvider::GitHub
+        } else {
+            GitProvider::Other
+        }
+    }
+
+    /// Authenticate an HTTPS `git clone`. GitLab takes the stored token as
+    /// `oauth2` basic auth; GitHub is left alone since the gh credential helper
+    /// supplies the token.
+    fn apply_clone_auth(self, command: &mut AsyncCommand) {
+        if self == GitProvider::GitLab {
+            if let Some(token) = get_gitlab_token() {
+                with_token_auth(command, &format!("https://{}/", GITLAB_HOST), "oauth2", &token);
+            }
+        }
+    }
+}
+
+/// `owner/repo` of a GitHub URL. URLs for other hosts (GitLab included) are
+/// rejected, since the result is used to address the GitHub API.
+pub(crate) fn parse_repo_full_name(url: &str) -> Result<String, String> {
+    // Handle various URL formats (host is github.com unless HATCH_GITHUB_BASE is set):
     // https://github.com/owner/repo
     // https://github.com/owner/repo.git
     // git@github.com:owner/repo.git
 
     let url = url.trim();
+    let host = github_host()?;
 
-    if url.starts_with("https://github.com/") {
-        let path = url.trim_start_matches("https://github.com/");
+    if let Some(path) = url.strip_prefix(&format!("https://{}/", host)) {
         let path = path.trim_end_matches(".git");
         return Ok(path.to_string());
     }
 
-    if url.starts_with("git@github.com:") {
-        let path = url.trim_start_matches("git@github.com:");
+    if let Some(path) = url.strip_prefix(&format!("git@{}:", host)) {
         let path = path.trim_end_matches(".git");
         return Ok(path.to_string());
     }
@@ -1061,6 +3839,62 @@ pub struct PullRequestInfo {
     pub mergeable: Option<bool>,
     pub mergeable_state: String, // "clean" | "dirty" | "blocked" | "behind" | "unstable"
     pub html_url: String,
+    pub author: Option<String>,
+    pub head_branch: Option<String>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitHubPRUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubPRRef {
+    #[serde(rename = "ref")]
+    ref_name: String,
+}
+
+/// A pull request as returned by the list endpoint
+#[derive(Deserialize)]
+struct GitHubPRListItem {
+    number: u32,
+    title: String,
+    state: String,
+    merged_at: Option<String>,
+    html_url: String,
+    user: Option<GitHubPRUser>,
+    head: Option<GitHubPRRef>,
+    base: Option<GitHubPRRef>,
+}
+
+impl From<GitHubPRListItem> for PullRequestInfo {
+    fn from(pr: GitHubPRListItem) -> Self {
+        PullRequestInfo {
+            number: pr.number,
+            title: pr.title,
+            state: pr.state,
+            merged: pr.merged_at.is_some(),
+            // The list endpoint doesn't compute mergeability; use git_get_pr for that
+            mergeable: None,
+            mergeable_state: "unknown".to_string(),
+            html_url: pr.html_url,
+            author: pr.user.map(|user| user.login),
+            head_branch: pr.head.map(|head| head.ref_name),
+            base_branch: pr.base.map(|base| base.ref_name),
+        }
+    }
+}
+
+/// Most pages `github_list_prs` will follow (100 PRs each)
+const MAX_PR_PAGES: usize = 10;
+
+/// Pull requests listed by `github_list_prs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestList {
+    pub prs: Vec<PullRequestInfo>,
+    /// More PRs exist past the `MAX_PR_PAGES` that were fetched
+    pub truncated: bool,
 }
 
 /// Result of merging a pull request
@@ -1079,6 +3913,7 @@ pub async fn git_get_pr(
 ) -> Result<PullRequestInfo, String> {
     let token = get_access_token().await
         .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
 
     let client = reqwest::Client::new();
 
@@ -1091,14 +3926,17 @@ pub async fn git_get_pr(
         mergeable: Option<bool>,
         mergeable_state: Option<String>,
         html_url: String,
+        user: Option<GitHubPRUser>,
+        head: Option<GitHubPRRef>,
+        base: Option<GitHubPRRef>,
     }
 
-    let response = client
-        .get(format!("https://api.github.com/repos/{}/pulls/{}", repo_full_name, pr_number))
+    let request = client
+        .get(format!("{}/repos/{}/pulls/{}", api_base, repo_full_name, pr_number))
         .header("Authorization", format!("Bearer {}", token))
         .header("User-Agent", "hatch-desktop")
-        .header("Accept", "application/vnd.github.v3+json")
-        .send()
+        .header("Accept", "application/vnd.github.v3+json");
+    let response = send_github_request(request)
         .await
         .map_err(|e| format!("Failed to fetch PR: {}", e))?;
 
@@ -1120,6 +3958,466 @@ pub async fn git_get_pr(
         mergeable: pr.mergeable,
         mergeable_state: pr.mergeable_state.unwrap_or_else(|| "unknown".to_string()),
         html_url: pr.html_url,
+        author: pr.user.map(|user| user.login),
+        head_branch: pr.head.map(|head| head.ref_name),
+        base_branch: pr.base.map(|base| base.ref_name),
+    })
+}
+
+/// List pull requests for a repository. `state` is "open" (default), "closed" or "all".
+/// At most `MAX_PR_PAGES` pages are read; `truncated` says whether more remain.
+#[tauri::command]
+pub async fn github_list_prs(repo_full_name: String, state: Option<String>) -> Result<PullRequestList, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+    let state = state.unwrap_or_else(|| "open".to_string());
+    if !matches!(state.as_str(), "open" | "closed" | "all") {
+        return Err(format!("Invalid PR state: {}", state));
+    }
+
+    let client = reqwest::Client::new();
+
+    let mut prs = Vec::new();
+    let mut next_url = Some(format!(
+        "{}/repos/{}/pulls?state={}&per_page=100",
+        api_base, repo_full_name, state
+    ));
+
+    for _ in 0..MAX_PR_PAGES {
+        let Some(url) = next_url.take() else {
+            break;
+        };
+
+        let (page, next): (Vec<GitHubPRListItem>, _) =
+            github_get_json_page(&client, &token, &url, "pull requests").await?;
+        next_url = next;
+        prs.extend(page.into_iter().map(PullRequestInfo::from));
+    }
+
+    Ok(PullRequestList {
+        prs,
+        truncated: next_url.is_some(),
+    })
+}
+
+/// Find the open pull request whose head is `head_branch`, if any. A bare
+/// branch name is looked up in the repo owner's namespace; pass `owner:branch`
+/// for a PR opened from a fork.
+#[tauri::command]
+pub async fn github_find_pr_for_branch(
+    repo_full_name: String,
+    head_branch: String,
+) -> Result<Option<PullRequestInfo>, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+    let head = pr_head_filter(&repo_full_name, &head_branch)?;
+
+    let url = reqwest::Url::parse_with_params(
+        &format!("{}/repos/{}/pulls", api_base, repo_full_name),
+        &[("head", head.as_str()), ("state", "open"), ("per_page", "1")],
+    )
+    .map_err(|e| format!("Failed to build PR search URL: {}", e))?;
+
+    let client = reqwest::Client::new();
+    let prs: Vec<GitHubPRListItem> = github_get_json(&client, &token, url.as_str(), "pull requests").await?;
+    Ok(prs.into_iter().next().map(PullRequestInfo::from))
+}
+
+/// The `head` filter of the PR list endpoint, which must be `owner:branch`
+pub(crate) fn pr_head_filter(repo_full_name: &str, head_branch: &str) -> Result<String, String> {
+    let head_branch = head_branch.trim().trim_start_matches("refs/heads/");
+    if head_branch.is_empty() {
+        return Err("Branch name cannot be empty".to_string());
+    }
+    if head_branch.contains(':') {
+        return Ok(head_branch.to_string());
+    }
+    match repo_full_name.split_once('/') {
+        Some((owner, _)) if !owner.is_empty() => Ok(format!("{}:{}", owner, head_branch)),
+        _ => Err(format!("Invalid repository name: {}", repo_full_name)),
+    }
+}
+
+/// A single CI signal on a PR head: a legacy commit status or a check run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub source: String, // "status" | "check_run"
+    pub status: String, // "queued" | "in_progress" | "completed"
+    pub conclusion: Option<String>, // "success" | "failure" | "neutral" | "cancelled" | "skipped" | ...
+    pub url: Option<String>,
+}
+
+/// Aggregate CI state for a PR head commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrChecks {
+    pub state: String, // "success" | "pending" | "failure"
+    pub sha: String,
+    pub checks: Vec<CheckRun>,
+}
+
+/// Get combined commit statuses and check runs for a PR's head commit
+#[tauri::command]
+pub async fn github_get_pr_checks(repo_full_name: String, pr_number: u32) -> Result<PrChecks, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+
+    let client = reqwest::Client::new();
+
+    #[derive(Deserialize)]
+    struct PRHead {
+        head: PRHeadRef,
+    }
+
+    #[derive(Deserialize)]
+    struct PRHeadRef {
+        sha: String,
+    }
+
+    #[derive(Deserialize)]
+    struct CombinedStatus {
+        statuses: Vec<CommitStatus>,
+    }
+
+    #[derive(Deserialize)]
+    struct CommitStatus {
+        context: String,
+        state: String, // "success" | "pending" | "failure" | "error"
+        target_url: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct CheckRunsResponse {
+        check_runs: Vec<GitHubCheckRun>,
+    }
+
+    #[derive(Deserialize)]
+    struct GitHubCheckRun {
+        name: String,
+        status: String,
+        conclusion: Option<String>,
+        html_url: Option<String>,
+    }
+
+    let pr: PRHead = github_get_json(
+        &client,
+        &token,
+        &format!("{}/repos/{}/pulls/{}", api_base, repo_full_name, pr_number),
+        "PR",
+    )
+    .await?;
+    let sha = pr.head.sha;
+
+    // Follow every page of both: a failure on a later page must still fail the PR
+    let (status_pages, unread_statuses) = github_get_json_pages::<CombinedStatus>(
+        &client,
+        &token,
+        format!("{}/repos/{}/commits/{}/status?per_page=100", api_base, repo_full_name, sha),
+        "commit status",
+    )
+    .await?;
+    let (check_run_pages, unread_runs) = github_get_json_pages::<CheckRunsResponse>(
+        &client,
+        &token,
+        format!("{}/repos/{}/commits/{}/check-runs?per_page=100", api_base, repo_full_name, sha),
+        "check runs",
+    )
+    .await?;
+    let check_runs = check_run_pages.into_iter().flat_map(|page| page.check_runs);
+
+    let mut checks: Vec<CheckRun> = status_pages
+        .into_iter()
+        .flat_map(|page| page.statuses)
+        .map(|status| {
+            let pending = status.state == "pending";
+            CheckRun {
+                name: status.context,
+                source: "status".to_string(),
+                status: if pending { "in_progress" } else { "completed" }.to_string(),
+                conclusion: if pending { None } else { Some(status.state) },
+                url: status.target_url,
+            }
+        })
+        .collect();
+
+    checks.extend(check_runs.map(|run| CheckRun {
+        name: run.name,
+        source: "check_run".to_string(),
+        status: run.status,
+        conclusion: run.conclusion,
+        url: run.html_url,
+    }));
+
+    let state = match aggregate_check_state(&checks) {
+        // Checks past the page cap weren't seen, so they can't be vouched for
+        "success" if unread_statuses || unread_runs => "pending",
+        state => state,
+    };
+
+    Ok(PrChecks {
+        state: state.to_string(),
+        sha,
+        checks,
+    })
+}
+
+/// Most pages of statuses or check runs `github_get_pr_checks` will follow
+/// (100 each)
+const MAX_CHECK_PAGES: usize = 10;
+
+/// Any failing check fails the PR; otherwise anything unfinished keeps it pending.
+/// A commit with no checks at all counts as success.
+pub(crate) fn aggregate_check_state(checks: &[CheckRun]) -> &'static str {
+    let failed = checks.iter().any(|check| {
+        matches!(
+            check.conclusion.as_deref(),
+            Some("failure" | "error" | "cancelled" | "timed_out" | "action_required" | "startup_failure")
+        )
+    });
+    if failed {
+        return "failure";
+    }
+
+    if checks.iter().any(|check| check.status != "completed") {
+        return "pending";
+    }
+
+    "success"
+}
+
+/// Unified diff of a PR along with per-file stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrDiff {
+    pub diff: String,
+    pub files: Vec<FileChange>,
+}
+
+/// Get a PR's unified diff from GitHub. Diffs too large for the diff media type
+/// (GitHub answers 406) are rebuilt from the per-file patches of the files API.
+#[tauri::command]
+pub async fn github_get_pr_diff(repo_full_name: String, pr_number: u32) -> Result<PrDiff, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+
+    let client = reqwest::Client::new();
+
+    let request = client
+        .get(format!("{}/repos/{}/pulls/{}", api_base, repo_full_name, pr_number))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3.diff");
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to fetch PR diff: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_ACCEPTABLE {
+        return pr_diff_from_files(&client, &token, &api_base, &repo_full_name, pr_number).await;
+    }
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
+    }
+
+    let diff = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read PR diff: {}", e))?;
+    let files = parse_unified_diff(&diff);
+
+    Ok(PrDiff { diff, files })
+}
+
+async fn pr_diff_from_files(
+    client: &reqwest::Client,
+    token: &str,
+    api_base: &str,
+    repo_full_name: &str,
+    pr_number: u32,
+) -> Result<PrDiff, String> {
+    #[derive(Deserialize)]
+    struct PRFile {
+        filename: String,
+        status: String, // "added" | "removed" | "modified" | "renamed" | "copied" | "changed" | "unchanged"
+        additions: u32,
+        deletions: u32,
+        previous_filename: Option<String>,
+        patch: Option<String>, // Omitted for binary or very large files
+    }
+
+    let mut diff = String::new();
+    let mut files = Vec::new();
+
+    // The files API caps out at 3000 files (30 pages of 100)
+    for page in 1..=30 {
+        let batch: Vec<PRFile> = github_get_json(
+            client,
+            token,
+            &format!(
+                "{}/repos/{}/pulls/{}/files?per_page=100&page={}",
+                api_base, repo_full_name, pr_number, page
+            ),
+            "PR files",
+        )
+        .await?;
+        let is_last = batch.len() < 100;
+
+        for file in batch {
+            let old_path = file.previous_filename.clone().unwrap_or_else(|| file.filename.clone());
+            diff.push_str(&format!("diff --git a/{} b/{}\n", old_path, file.filename));
+            if let Some(patch) = &file.patch {
+                let from = if file.status == "added" { "/dev/null".to_string() } else { format!("a/{}", old_path) };
+                let to = if file.status == "removed" { "/dev/null".to_string() } else { format!("b/{}", file.filename) };
+                diff.push_str(&format!("--- {}\n+++ {}\n{}\n", from, to, patch));
+            }
+
+            files.push(FileChange {
+                path: file.filename,
+                old_path: file.previous_filename,
+                additions: file.additions,
+                deletions: file.deletions,
+                status: match file.status.as_str() {
+                    "added" => "added",
+                    "removed" => "deleted",
+                    "renamed" => "renamed",
+                    _ => "modified",
+                }
+                .to_string(),
+            });
+        }
+
+        if is_last {
+            break;
+        }
+    }
+
+    Ok(PrDiff { diff, files })
+}
+
+/// Per-file stats from a unified `diff --git` patch
+pub(crate) fn parse_unified_diff(diff: &str) -> Vec<FileChange> {
+    let mut files: Vec<FileChange> = Vec::new();
+    let mut in_hunk = false;
+
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            in_hunk = false;
+            // Best-effort path from the header; ---/+++ and rename lines refine it
+            let path = rest
+                .split_once(" b/")
+                .map(|(_, new)| new.to_string())
+                .unwrap_or_else(|| rest.to_string());
+            files.push(FileChange {
+                path,
+                old_path: None,
+                additions: 0,
+                deletions: 0,
+                status: "modified".to_string(),
+            });
+            continue;
+        }
+
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if in_hunk {
+            if line.starts_with('+') {
+                file.additions += 1;
+            } else if line.starts_with('-') {
+                file.deletions += 1;
+            }
+        } else if line.starts_with("new file mode") {
+            file.status = "added".to_string();
+        } else if line.starts_with("deleted file mode") {
+            file.status = "deleted".to_string();
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.status = "renamed".to_string();
+            file.old_path = Some(from.to_string());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.path = to.to_string();
+        } else if let Some(to) = line.strip_prefix("+++ b/") {
+            file.path = to.to_string();
+        }
+    }
+
+    files
+}
+
+/// GET a GitHub API resource and decode it, reporting rate limits and API errors
+async fn github_get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+    what: &str,
+) -> Result<T, String> {
+    github_get_json_page(client, token, url, what).await.map(|(value, _)| value)
+}
+
+/// Like `github_get_json`, also returning the URL of the next page, if any
+async fn github_get_json_page<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    url: &str,
+    what: &str,
+) -> Result<(T, Option<String>), String> {
+    let request = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3+json");
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", what, e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
+    }
+
+    let next = next_page_url(response.headers());
+    let value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse {} response: {}", what, e))?;
+    Ok((value, next))
+}
+
+/// Fetch up to `MAX_CHECK_PAGES` pages starting at `url`. Also returns whether
+/// more pages were left unread.
+async fn github_get_json_pages<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: &str,
+    url: String,
+    what: &str,
+) -> Result<(Vec<T>, bool), String> {
+    let mut pages = Vec::new();
+    let mut next_url = Some(url);
+    for _ in 0..MAX_CHECK_PAGES {
+        let Some(url) = next_url.take() else {
+            break;
+        };
+        let (page, next) = github_get_json_page(client, token, &url, what).await?;
+        next_url = next;
+        pages.push(page);
+    }
+    Ok((pages, next_url.is_some()))
+}
+
+/// Follow the `rel="next"` entry of a GitHub `Link` header
+pub(crate) fn next_page_url(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let link = headers.get(reqwest::header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        if !params.contains("rel=\"next\"") {
+            return None;
+        }
+        Some(url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
     })
 }
 
@@ -1132,6 +4430,7 @@ pub async fn git_merge_pr(
 ) -> Result<MergeResult, String> {
     let token = get_access_token().await
         .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
 
     let client = reqwest::Client::new();
 
@@ -1147,15 +4446,15 @@ pub async fn git_merge_pr(
         sha: Option<String>,
     }
 
-    let response = client
-        .put(format!("https://api.github.com/repos/{}/pulls/{}/merge", repo_full_name, pr_number))
+    let request = client
+        .put(format!("{}/repos/{}/pulls/{}/merge", api_base, repo_full_name, pr_number))
         .header("Authorization", format!("Bearer {}", token))
         .header("User-Agent", "hatch-desktop")
         .header("Accept", "application/vnd.github.v3+json")
         .json(&MergeRequest {
             merge_method,
-        })
-        .send()
+        });
+    let response = send_github_request(request)
         .await
         .map_err(|e| format!("Failed to merge PR: {}", e))?;
 
@@ -1176,27 +4475,59 @@ pub async fn git_merge_pr(
     })
 }
 
-// We need uuid for generating workspace IDs
-mod uuid {
-    pub struct Uuid;
+/// Issue created via the GitHub API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueInfo {
+    pub number: u64,
+    pub html_url: String,
+}
 
-    impl Uuid {
-        pub fn new_v4() -> UuidResult {
-            use std::time::{SystemTime, UNIX_EPOCH};
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_nanos();
-            let random: u64 = now as u64 ^ (std::process::id() as u64);
-            UuidResult(format!("{:016x}", random))
-        }
+/// Create an issue using GitHub API
+#[tauri::command]
+pub async fn github_create_issue(
+    repo_full_name: String,
+    title: String,
+    body: String,
+    labels: Vec<String>,
+    assignees: Option<Vec<String>>,
+) -> Result<IssueInfo, String> {
+    let token = get_access_token().await
+        .ok_or("Not authenticated with GitHub. Please sign in first.")?;
+    let api_base = github_api_base()?;
+
+    let client = reqwest::Client::new();
+
+    #[derive(Serialize)]
+    struct CreateIssueRequest {
+        title: String,
+        body: String,
+        labels: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        assignees: Option<Vec<String>>,
     }
 
-    pub struct UuidResult(String);
+    let request = client
+        .post(format!("{}/repos/{}/issues", api_base, repo_full_name))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3+json")
+        .json(&CreateIssueRequest {
+            title,
+            body,
+            labels,
+            assignees,
+        });
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to create issue: {}", e))?;
 
-    impl UuidResult {
-        pub fn to_string(&self) -> String {
-            self.0.clone()
-        }
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
     }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse issue response: {}", e))
 }
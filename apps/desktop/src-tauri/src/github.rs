@@ -1,8 +1,25 @@
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::process::Command as AsyncCommand;
 
+use crate::keychain::{keychain_delete, keychain_get, keychain_set};
+
+/// Keychain key holding the GitHub access token
+const TOKEN_KEYCHAIN_KEY: &str = "github_access_token";
+/// Error `fetch_user_with_token` returns when GitHub rejects the token
+const BAD_CREDENTIALS: &str = "Bad credentials: the token is invalid or expired";
+
+const DEFAULT_GITHUB_BASE: &str = "https://github.com";
+const DEFAULT_GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Attempts per GitHub API request before a 5xx or rate limit is surfaced
+const MAX_REQUEST_ATTEMPTS: u32 = 3;
+/// Longest we sleep for a rate limit reset before giving up
+const MAX_RATE_LIMIT_WAIT_SECS: u64 = 60;
+const SERVER_ERROR_BACKOFF_MS: u64 = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubUser {
     pub login: String,
@@ -17,6 +34,12 @@ pub struct GitHubAuthState {
     pub access_token: Option<String>,
     pub user: Option<GitHubUser>,
     pub is_authenticated: bool,
+    /// OAuth scopes granted to the token; empty when GitHub doesn't report them
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Set at sign-in when the token lacks scopes needed for PRs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_warning: Option<String>,
 }
 
 impl Default for GitHubAuthState {
@@ -25,7 +48,88 @@ impl Default for GitHubAuthState {
             access_token: None,
             user: None,
             is_authenticated: false,
+            scopes: Vec::new(),
+            scope_warning: None,
+        }
+    }
+}
+
+/// Web base URL, overridable via HATCH_GITHUB_BASE for GitHub Enterprise Server
+pub fn github_web_base() -> Result<String, String> {
+    resolve_base_url("HATCH_GITHUB_BASE", DEFAULT_GITHUB_BASE)
+}
+
+/// API base URL, overridable via HATCH_GITHUB_API_BASE (e.g. https://ghe.example.com/api/v3)
+pub fn github_api_base() -> Result<String, String> {
+    resolve_base_url("HATCH_GITHUB_API_BASE", DEFAULT_GITHUB_API_BASE)
+}
+
+/// Host name of the web base URL, as expected by `gh --hostname`
+pub fn github_host() -> Result<String, String> {
+    let base = github_web_base()?;
+    let without_scheme = base.split_once("://").map(|(_, rest)| rest).unwrap_or(&base);
+    Ok(without_scheme.split('/').next().unwrap_or(without_scheme).to_string())
+}
+
+fn resolve_base_url(var: &str, default: &str) -> Result<String, String> {
+    match env::var(var) {
+        Ok(value) if !value.trim().is_empty() => {
+            validate_base_url(value.trim()).map_err(|e| format!("Invalid {}: {}", var, e))
         }
+        _ => Ok(default.to_string()),
+    }
+}
+
+fn validate_base_url(url: &str) -> Result<String, String> {
+    let trimmed = url.trim_end_matches('/');
+    let rest = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .ok_or_else(|| format!("'{}' must start with http:// or https://", url))?;
+
+    if rest.is_empty() || rest.starts_with('/') {
+        return Err(format!("'{}' is missing a host", url));
+    }
+
+    Ok(trimmed.to_string())
+}
+
+/// Token saved by `github_login_with_token`, if any
+fn load_stored_token() -> Option<String> {
+    keychain_get(TOKEN_KEYCHAIN_KEY.to_string(), None)
+        .ok()
+        .flatten()
+        .filter(|token| !token.is_empty())
+}
+
+fn clear_stored_token() -> Result<(), String> {
+    keychain_delete(TOKEN_KEYCHAIN_KEY.to_string(), None)
+}
+
+/// Auth state for the keychain token, with the user fetched from the API. A
+/// token GitHub rejects is deleted so it isn't tried again; one that can't be
+/// checked (e.g. offline) is kept without a user.
+async fn stored_token_auth_state() -> Option<GitHubAuthState> {
+    let token = load_stored_token()?;
+    match fetch_user_with_token(&token).await {
+        Ok((user, scopes)) => Some(GitHubAuthState {
+            access_token: Some(token),
+            user: Some(user),
+            is_authenticated: true,
+            scopes: scopes.unwrap_or_default(),
+            scope_warning: None,
+        }),
+        Err(error) if error == BAD_CREDENTIALS => {
+            let _ = clear_stored_token();
+            None
+        }
+        Err(_) => Some(GitHubAuthState {
+            access_token: Some(token),
+            user: None,
+            is_authenticated: true,
+            scopes: Vec::new(),
+            scope_warning: None,
+        }),
     }
 }
 
@@ -80,24 +184,27 @@ pub async fn github_check_gh_installed() -> Result<bool, String> {
     Ok(find_gh_path().await.is_some())
 }
 
-/// Get the current auth state by querying gh CLI
+/// Get the current auth state from the gh CLI, falling back to a token saved
+/// with `github_login_with_token`
 #[tauri::command]
 pub async fn github_get_auth_state() -> Result<GitHubAuthState, String> {
     let gh_path = match find_gh_path().await {
         Some(path) => path,
-        None => return Ok(GitHubAuthState::default()),
+        None => return Ok(stored_token_auth_state().await.unwrap_or_default()),
     };
 
+    let host = github_host()?;
+
     // Check auth status by fetching user info
     let output = AsyncCommand::new(&gh_path)
-        .args(["api", "/user"])
+        .args(["api", "/user", "--hostname", &host])
         .output()
         .await
         .map_err(|e| format!("Failed to run gh api: {}", e))?;
 
     if !output.status.success() {
         // Not authenticated or gh not configured
-        return Ok(GitHubAuthState::default());
+        return Ok(stored_token_auth_state().await.unwrap_or_default());
     }
 
     let user: GitHubUser = serde_json::from_slice(&output.stdout)
@@ -110,6 +217,8 @@ pub async fn github_get_auth_state() -> Result<GitHubAuthState, String> {
         access_token: token,
         user: Some(user),
         is_authenticated: true,
+        scopes: Vec::new(),
+        scope_warning: None,
     })
 }
 
@@ -119,9 +228,11 @@ pub async fn github_login() -> Result<GitHubAuthState, String> {
     let gh_path = find_gh_path().await
         .ok_or("gh CLI is not installed. Install it from https://cli.github.com")?;
 
+    let host = github_host()?;
+
     // Run gh auth login --web -s repo
     let output = AsyncCommand::new(&gh_path)
-        .args(["auth", "login", "--web", "-h", "github.com", "-s", "repo"])
+        .args(["auth", "login", "--web", "-h", &host, "-s", "repo"])
         .output()
         .await
         .map_err(|e| format!("Failed to run gh auth login: {}", e))?;
@@ -132,19 +243,234 @@ pub async fn github_login() -> Result<GitHubAuthState, String> {
     }
 
     // Fetch user info after successful login
-    github_get_auth_state().await
+    let mut state = github_get_auth_state().await?;
+
+    if let Some(token) = &state.access_token {
+        if let Ok((_, Some(scopes))) = fetch_user_with_token(token).await {
+            state.scope_warning = repo_scope_warning(&scopes);
+            state.scopes = scopes;
+        }
+    }
+
+    Ok(state)
+}
+
+/// Log in with a personal access token, bypassing the browser flow
+#[tauri::command]
+pub async fn github_login_with_token(token: String) -> Result<GitHubAuthState, String> {
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err("Token must not be empty".to_string());
+    }
+
+    let (user, scopes) = fetch_user_with_token(&token).await?;
+
+    // Classic tokens report their scopes; fine-grained tokens omit the header
+    if let Some(warning) = scopes.as_deref().and_then(repo_scope_warning) {
+        return Err(warning);
+    }
+
+    let state = GitHubAuthState {
+        access_token: Some(token),
+        user: Some(user),
+        is_authenticated: true,
+        scopes: scopes.unwrap_or_default(),
+        scope_warning: None,
+    };
+    keychain_set(TOKEN_KEYCHAIN_KEY.to_string(), state.access_token.clone().unwrap_or_default(), None)?;
+
+    Ok(state)
+}
+
+/// Fetch the user for a token, along with its scopes if GitHub reports them
+async fn fetch_user_with_token(token: &str) -> Result<(GitHubUser, Option<Vec<String>>), String> {
+    let api_base = github_api_base()?;
+
+    let request = reqwest::Client::new()
+        .get(format!("{}/user", api_base))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "hatch-desktop")
+        .header("Accept", "application/vnd.github.v3+json");
+    let response = send_github_request(request)
+        .await
+        .map_err(|e| format!("Failed to validate token: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(BAD_CREDENTIALS.to_string());
+    }
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitHub API error: {}", error_text));
+    }
+
+    let scopes = response
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|value| value.to_str().ok())
+        .map(parse_scopes);
+
+    let user: GitHubUser = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse user info: {}", e))?;
+
+    Ok((user, scopes))
+}
+
+/// Split an `X-OAuth-Scopes` header ("repo, workflow") into scope names
+fn parse_scopes(header: &str) -> Vec<String> {
+    header
+        .split(',')
+        .map(|scope| scope.trim())
+        .filter(|scope| !scope.is_empty())
+        .map(|scope| scope.to_string())
+        .collect()
+}
+
+/// Describe a token that can't push or open PRs because it lacks `repo`
+fn repo_scope_warning(scopes: &[String]) -> Option<String> {
+    if scopes.iter().any(|scope| scope == "repo") {
+        return None;
+    }
+    Some(format!(
+        "Token is missing the 'repo' scope (has: {})",
+        if scopes.is_empty() { "none".to_string() } else { scopes.join(", ") }
+    ))
+}
+
+/// Why a GitHub API request could not be completed
+#[derive(Debug)]
+pub enum GitHubRequestError {
+    /// The rate limit is exhausted and resets too far out to wait for
+    RateLimited { reset_at: Option<u64> },
+    Transport(String),
+}
+
+impl std::fmt::Display for GitHubRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitHubRequestError::RateLimited { reset_at: Some(reset_at) } => write!(
+                f,
+                "GitHub API rate limit exceeded (resets at unix time {})",
+                reset_at
+            ),
+            GitHubRequestError::RateLimited { reset_at: None } => {
+                write!(f, "GitHub API rate limit exceeded")
+            }
+            GitHubRequestError::Transport(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Send a GitHub API request, waiting out short rate limits and retrying 5xx
+/// responses of idempotent requests with exponential backoff. Other responses
+/// are returned as-is.
+pub async fn send_github_request(
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, GitHubRequestError> {
+    let method = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .map(|request| request.method().clone());
+    let mut attempt = 1;
+    loop {
+        let Some(this_attempt) = request.try_clone() else {
+            // Streaming bodies can't be replayed, so send them exactly once
+            return request
+                .send()
+                .await
+                .map_err(|e| GitHubRequestError::Transport(e.to_string()));
+        };
+
+        let response = this_attempt
+            .send()
+            .await
+            .map_err(|e| GitHubRequestError::Transport(e.to_string()))?;
+
+        if let Some((wait, reset_at)) = rate_limit_wait(&response) {
+            if attempt >= MAX_REQUEST_ATTEMPTS || wait.as_secs() > MAX_RATE_LIMIT_WAIT_SECS {
+                return Err(GitHubRequestError::RateLimited { reset_at });
+            }
+            tokio::time::sleep(wait).await;
+        } else if let Some(backoff) = method
+            .as_ref()
+            .and_then(|method| server_error_backoff(method, response.status(), attempt))
+        {
+            tokio::time::sleep(backoff).await;
+        } else {
+            return Ok(response);
+        }
+
+        attempt += 1;
+    }
+}
+
+/// How long to wait before retrying a 5xx response, or `None` to return it.
+/// Only reads are retried: a 502 on a POST may arrive after GitHub already
+/// created the issue or repo, so replaying it would duplicate the write.
+pub(crate) fn server_error_backoff(
+    method: &reqwest::Method,
+    status: reqwest::StatusCode,
+    attempt: u32,
+) -> Option<Duration> {
+    let idempotent = matches!(*method, reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::OPTIONS);
+    if !idempotent || !status.is_server_error() || attempt >= MAX_REQUEST_ATTEMPTS {
+        return None;
+    }
+    Some(Duration::from_millis(SERVER_ERROR_BACKOFF_MS << (attempt - 1)))
+}
+
+/// How long to wait before retrying a rate-limited response, and when the limit
+/// resets. `None` when the response isn't a rate limit.
+fn rate_limit_wait(response: &reqwest::Response) -> Option<(Duration, Option<u64>)> {
+    let status = response.status().as_u16();
+    if status != 403 && status != 429 {
+        return None;
+    }
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+    };
+
+    let reset_at = header("x-ratelimit-reset").and_then(|reset| reset.parse::<u64>().ok());
+
+    // Secondary rate limits send Retry-After instead of exhausting the quota
+    if let Some(retry_after) = header("retry-after").and_then(|secs| secs.parse::<u64>().ok()) {
+        return Some((Duration::from_secs(retry_after), reset_at));
+    }
+
+    if header("x-ratelimit-remaining").as_deref() != Some("0") {
+        return None;
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    // GitHub's reset timestamp has second granularity; add one to be safe
+    let wait = reset_at.map_or(u64::MAX, |reset| reset.saturating_sub(now) + 1);
+    Some((Duration::from_secs(wait), reset_at))
 }
 
 /// Sign out from GitHub via gh CLI
 #[tauri::command]
 pub async fn github_sign_out() -> Result<(), String> {
+    clear_stored_token()?;
+
     let gh_path = match find_gh_path().await {
         Some(path) => path,
         None => return Ok(()), // Nothing to sign out from
     };
 
+    let host = github_host()?;
+
     let output = AsyncCommand::new(&gh_path)
-        .args(["auth", "logout", "--hostname", "github.com"])
+        .args(["auth", "logout", "--hostname", &host])
         .stdin(std::process::Stdio::null())
         .output()
         .await
@@ -161,32 +487,37 @@ pub async fn github_sign_out() -> Result<(), String> {
     Ok(())
 }
 
-/// Validate the current auth by fetching user info
+/// Validate the current auth by fetching user info and granted scopes
 #[tauri::command]
-pub async fn github_validate_token() -> Result<GitHubUser, String> {
-    let gh_path = find_gh_path().await
-        .ok_or("gh CLI is not installed")?;
+pub async fn github_validate_token() -> Result<GitHubAuthState, String> {
+    let token = get_access_token().await.ok_or("Not authenticated")?;
+    let (user, scopes) = fetch_user_with_token(&token).await?;
+    let scopes = scopes.unwrap_or_default();
 
-    let output = AsyncCommand::new(&gh_path)
-        .args(["api", "/user"])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to validate token: {}", e))?;
+    Ok(GitHubAuthState {
+        access_token: Some(token),
+        user: Some(user),
+        is_authenticated: true,
+        scopes,
+        scope_warning: None,
+    })
+}
 
-    if !output.status.success() {
-        return Err("Not authenticated".to_string());
+/// Get access token for API calls: the gh CLI's live token, falling back to
+/// one saved with `github_login_with_token`
+pub async fn get_access_token() -> Option<String> {
+    if let Some(token) = gh_auth_token().await {
+        return Some(token);
     }
-
-    serde_json::from_slice(&output.stdout)
-        .map_err(|e| format!("Failed to parse user info: {}", e))
+    load_stored_token()
 }
 
-/// Get access token from gh CLI for API calls
-pub async fn get_access_token() -> Option<String> {
+async fn gh_auth_token() -> Option<String> {
     let gh_path = find_gh_path().await?;
+    let host = github_host().ok()?;
 
     let output = AsyncCommand::new(&gh_path)
-        .args(["auth", "token"])
+        .args(["auth", "token", "--hostname", &host])
         .output()
         .await
         .ok()?;
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::keychain::keychain_get;
+
+/// Keychain key holding the GitLab personal access token
+pub const GITLAB_TOKEN_KEYCHAIN_KEY: &str = "gitlab_access_token";
+
+pub const GITLAB_HOST: &str = "gitlab.com";
+const GITLAB_API_BASE: &str = "https://gitlab.com/api/v4";
+
+/// GitLab token from the keychain, falling back to the `GITLAB_TOKEN` env var
+pub fn get_gitlab_token() -> Option<String> {
+    keychain_get(GITLAB_TOKEN_KEYCHAIN_KEY.to_string(), None)
+        .ok()
+        .flatten()
+        .or_else(|| std::env::var("GITLAB_TOKEN").ok())
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Project paths (`group/subgroup/repo`) are passed to the API URL-encoded
+fn encode_project_path(project: &str) -> String {
+    project
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Create a merge request, returning its web URL
+#[tauri::command]
+pub async fn gitlab_create_mr(
+    project: String,
+    source_branch: String,
+    target_branch: String,
+    title: String,
+    description: String,
+) -> Result<String, String> {
+    let token = get_gitlab_token()
+        .ok_or("Not authenticated with GitLab. Please add a GitLab access token first.")?;
+
+    let client = reqwest::Client::new();
+
+    #[derive(Serialize)]
+    struct CreateMRRequest {
+        source_branch: String,
+        target_branch: String,
+        title: String,
+        description: String,
+    }
+
+    #[derive(Deserialize)]
+    struct CreateMRResponse {
+        web_url: String,
+    }
+
+    let response = client
+        .post(format!(
+            "{}/projects/{}/merge_requests",
+            GITLAB_API_BASE,
+            encode_project_path(&project)
+        ))
+        .header("PRIVATE-TOKEN", token)
+        .header("User-Agent", "hatch-desktop")
+        .json(&CreateMRRequest {
+            source_branch,
+            target_branch,
+            title,
+            description,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create MR: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("GitLab API error: {}", error_text));
+    }
+
+    let mr_response: CreateMRResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse MR response: {}", e))?;
+
+    Ok(mr_response.web_url)
+}
@@ -1,18 +1,63 @@
 /// OS keychain integration via the `keyring` crate.
 /// Stores secrets (API keys, tokens) in macOS Keychain / Linux Secret Service / Windows Credential Manager.
 
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
 const SERVICE: &str = "sh.hatch.desktop";
+/// Entry holding a JSON list of the keys this app has stored. Not every keyring
+/// backend can enumerate, so `keychain_list` reads this instead.
+const INDEX_KEY: &str = "__hatch_keychain_index";
+/// Serializes read-modify-write updates of the index entry
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+/// Env values of the form `keychain:<key>` are read from the keychain
+const ENV_KEYCHAIN_PREFIX: &str = "keychain:";
+
+/// Full entry name for `key`, scoped under `namespace` when given. Account-scoped
+/// secrets use a namespace like `account:<login>`, giving `account:<login>:token`.
+fn scoped_key(key: &str, namespace: Option<&str>) -> String {
+    match namespace.filter(|namespace| !namespace.is_empty()) {
+        Some(namespace) => format!("{}:{}", namespace, key),
+        None => key.to_string(),
+    }
+}
+
+fn read_index() -> Result<BTreeSet<String>, String> {
+    let entry = keyring::Entry::new(SERVICE, INDEX_KEY).map_err(|e| e.to_string())?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json).unwrap_or_default()),
+        Err(keyring::Error::NoEntry) => Ok(BTreeSet::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Add or remove `key` in the index entry
+fn update_index(key: &str, present: bool) -> Result<(), String> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut keys = read_index()?;
+    let changed = if present { keys.insert(key.to_string()) } else { keys.remove(key) };
+    if !changed {
+        return Ok(());
+    }
+
+    let json = serde_json::to_string(&keys).map_err(|e| e.to_string())?;
+    let entry = keyring::Entry::new(SERVICE, INDEX_KEY).map_err(|e| e.to_string())?;
+    entry.set_password(&json).map_err(|e| e.to_string())
+}
 
 /// Store a value in the OS keychain.
 #[tauri::command]
-pub fn keychain_set(key: String, value: String) -> Result<(), String> {
+pub fn keychain_set(key: String, value: String, namespace: Option<String>) -> Result<(), String> {
+    let key = scoped_key(&key, namespace.as_deref());
     let entry = keyring::Entry::new(SERVICE, &key).map_err(|e| e.to_string())?;
-    entry.set_password(&value).map_err(|e| e.to_string())
+    entry.set_password(&value).map_err(|e| e.to_string())?;
+    update_index(&key, true)
 }
 
 /// Retrieve a value from the OS keychain. Returns None if the key doesn't exist.
 #[tauri::command]
-pub fn keychain_get(key: String) -> Result<Option<String>, String> {
+pub fn keychain_get(key: String, namespace: Option<String>) -> Result<Option<String>, String> {
+    let key = scoped_key(&key, namespace.as_deref());
     let entry = keyring::Entry::new(SERVICE, &key).map_err(|e| e.to_string())?;
     match entry.get_password() {
         Ok(password) => Ok(Some(password)),
@@ -23,18 +68,44 @@ pub fn keychain_get(key: String) -> Result<Option<String>, String> {
 
 /// Delete a value from the OS keychain. Silently succeeds if the key doesn't exist.
 #[tauri::command]
-pub fn keychain_delete(key: String) -> Result<(), String> {
+pub fn keychain_delete(key: String, namespace: Option<String>) -> Result<(), String> {
+    let key = scoped_key(&key, namespace.as_deref());
     let entry = keyring::Entry::new(SERVICE, &key).map_err(|e| e.to_string())?;
     match entry.delete_credential() {
-        Ok(()) => Ok(()),
-        Err(keyring::Error::NoEntry) => Ok(()),
+        Ok(()) | Err(keyring::Error::NoEntry) => update_index(&key, false),
         Err(e) => Err(e.to_string()),
     }
 }
 
+/// Delete every entry stored under `namespace`, as tracked by the index
+#[tauri::command]
+pub fn keychain_delete_namespace(namespace: String) -> Result<(), String> {
+    let prefix = scoped_key("", Some(&namespace));
+    for key in read_index()?.into_iter().filter(|key| key.starts_with(&prefix)) {
+        keychain_delete(key, None)?;
+    }
+    Ok(())
+}
+
+/// List the keys this app has stored (not their values), sorted. With a
+/// namespace, only that namespace's keys are listed, without the prefix. Keys
+/// written before the index existed show up once they are next set.
+#[tauri::command]
+pub fn keychain_list(namespace: Option<String>) -> Result<Vec<String>, String> {
+    let keys = read_index()?.into_iter();
+    match namespace.filter(|namespace| !namespace.is_empty()) {
+        Some(namespace) => {
+            let prefix = scoped_key("", Some(&namespace));
+            Ok(keys.filter_map(|key| key.strip_prefix(&prefix).map(|key| key.to_string())).collect())
+        }
+        None => Ok(keys.collect()),
+    }
+}
+
 /// Check if a key exists in the OS keychain with a non-empty value.
 #[tauri::command]
-pub fn keychain_has(key: String) -> Result<bool, String> {
+pub fn keychain_has(key: String, namespace: Option<String>) -> Result<bool, String> {
+    let key = scoped_key(&key, namespace.as_deref());
     let entry = keyring::Entry::new(SERVICE, &key).map_err(|e| e.to_string())?;
     match entry.get_password() {
         Ok(password) => Ok(!password.is_empty()),
@@ -42,3 +113,18 @@ pub fn keychain_has(key: String) -> Result<bool, String> {
         Err(e) => Err(e.to_string()),
     }
 }
+
+/// Resolve env vars for a spawned process, replacing `keychain:<key>` values with
+/// the stored secret so tokens aren't passed as plaintext from the frontend.
+pub fn resolve_env(env: Option<HashMap<String, String>>) -> Result<HashMap<String, String>, String> {
+    env.unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| match value.strip_prefix(ENV_KEYCHAIN_PREFIX) {
+            Some(key) => match keychain_get(key.to_string(), None)? {
+                Some(secret) => Ok((name, secret)),
+                None => Err(format!("Keychain key {} referenced by {} is not set", key, name)),
+            },
+            None => Ok((name, value)),
+        })
+        .collect()
+}
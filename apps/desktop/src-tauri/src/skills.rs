@@ -1,6 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::process::Command;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use tauri::{Emitter, State};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as AsyncCommand;
+
+use crate::keychain::resolve_env;
+use crate::StreamEvent;
 
 /// Result from skill installation
 #[derive(Serialize, Deserialize)]
@@ -17,6 +26,143 @@ pub struct SkillFile {
     pub content: String,
 }
 
+/// An installed skill along with metadata from its `skill.json` or `SKILL.md` front-matter
+#[derive(Serialize, Deserialize)]
+pub struct InstalledSkill {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub path: String,
+}
+
+/// Optional metadata a skill can declare about itself
+#[derive(Default)]
+struct SkillMetadata {
+    version: Option<String>,
+    description: Option<String>,
+}
+
+impl SkillMetadata {
+    /// `skill.json` wins over `SKILL.md` front-matter when both declare a field
+    fn from_sources(skill_json: Option<&str>, skill_md: Option<&str>) -> Self {
+        let json: Option<serde_json::Value> = skill_json.and_then(|content| serde_json::from_str(content).ok());
+        let json_field = |key: &str| {
+            json.as_ref()
+                .and_then(|value| value.get(key))
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        };
+        let front_matter = skill_md.map(parse_front_matter).unwrap_or_default();
+
+        Self {
+            version: json_field("version").or(front_matter.version),
+            description: json_field("description").or(front_matter.description),
+        }
+    }
+
+    fn from_dir(skill_dir: &Path) -> Self {
+        let skill_json = std::fs::read_to_string(skill_dir.join("skill.json")).ok();
+        let skill_md = std::fs::read_to_string(skill_dir.join("SKILL.md")).ok();
+        Self::from_sources(skill_json.as_deref(), skill_md.as_deref())
+    }
+
+    fn from_files(files: &[SkillFile]) -> Self {
+        let content = |name: &str| files.iter().find(|file| file.name == name).map(|file| file.content.as_str());
+        Self::from_sources(content("skill.json"), content("SKILL.md"))
+    }
+}
+
+/// Read `version`/`description` from a `---` delimited YAML front-matter block
+fn parse_front_matter(content: &str) -> SkillMetadata {
+    let mut metadata = SkillMetadata::default();
+    let mut lines = content.trim_start_matches('\u{feff}').lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return metadata;
+    }
+
+    for line in lines {
+        if line.trim_end() == "---" {
+            break;
+        }
+        // Only top-level keys; nested YAML is indented
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim() {
+            "version" => metadata.version = Some(value),
+            "description" => metadata.description = Some(value),
+            _ => {}
+        }
+    }
+
+    metadata
+}
+
+/// Compare dotted versions numerically ("1.10.0" > "1.9"), ignoring a leading `v`
+/// and any pre-release/build suffix
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parts(a), parts(b));
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Write skill files into `skill_dir`, creating nested directories as needed
+fn write_skill_files(skill_dir: &Path, files: &[SkillFile]) -> Result<(), String> {
+    std::fs::create_dir_all(skill_dir).map_err(|e| format!("Failed to create skill directory: {}", e))?;
+
+    for file in files {
+        let file_path = skill_dir.join(&file.name);
+
+        // Create parent directories if needed (for nested files)
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory for {}: {}", file.name, e))?;
+        }
+
+        std::fs::write(&file_path, &file.content).map_err(|e| format!("Failed to write {}: {}", file.name, e))?;
+    }
+
+    Ok(())
+}
+
+/// Skill names become a directory under the skills dir, so they must name a
+/// single plain path component
+fn validate_skill_name(skill_name: &str) -> Result<(), String> {
+    if skill_name.is_empty() || skill_name.starts_with('.') || skill_name.contains(['/', '\\', ':']) {
+        return Err(format!("Invalid skill name: {}", skill_name));
+    }
+    Ok(())
+}
+
+/// Millisecond stamp that keeps staging and backup directory names unique
+fn staging_stamp() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
 /// Get the skills directory path
 fn get_skills_dir(is_global: bool, working_directory: Option<String>) -> Result<PathBuf, String> {
     if is_global {
@@ -42,6 +188,14 @@ pub async fn install_skill(
     is_global: bool,
     working_directory: Option<String>,
 ) -> SkillInstallResult {
+    if let Err(e) = validate_skill_name(&skill_name) {
+        return SkillInstallResult {
+            success: false,
+            message: e,
+            path: None,
+        };
+    }
+
     // Get the target directory
     let base_dir = match get_skills_dir(is_global, working_directory) {
         Ok(dir) => dir,
@@ -56,45 +210,347 @@ pub async fn install_skill(
 
     let skill_dir = base_dir.join(&skill_name);
 
-    // Create the directory structure
-    if let Err(e) = std::fs::create_dir_all(&skill_dir) {
+    if let Err(e) = write_skill_files(&skill_dir, &files) {
         return SkillInstallResult {
             success: false,
-            message: format!("Failed to create skill directory: {}", e),
+            message: e,
             path: None,
         };
     }
 
-    // Write all files
-    for file in files {
-        let file_path = skill_dir.join(&file.name);
+    let installed_path = skill_dir.to_string_lossy().to_string();
 
-        // Create parent directories if needed (for nested files)
-        if let Some(parent) = file_path.parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                return SkillInstallResult {
-                    success: false,
-                    message: format!("Failed to create directory for {}: {}", file.name, e),
-                    path: None,
-                };
-            }
-        }
+    SkillInstallResult {
+        success: true,
+        message: format!("Successfully installed {} skill", skill_name),
+        path: Some(installed_path),
+    }
+}
+
+/// Update an installed skill, rewriting its files only when the incoming version
+/// is newer than the installed one (or `force` is set). Installs it if missing.
+#[tauri::command]
+pub async fn update_skill(
+    skill_name: String,
+    files: Vec<SkillFile>,
+    is_global: bool,
+    working_directory: Option<String>,
+    force: Option<bool>,
+) -> SkillInstallResult {
+    if let Err(e) = validate_skill_name(&skill_name) {
+        return SkillInstallResult {
+            success: false,
+            message: e,
+            path: None,
+        };
+    }
 
-        if let Err(e) = std::fs::write(&file_path, &file.content) {
+    let base_dir = match get_skills_dir(is_global, working_directory.clone()) {
+        Ok(dir) => dir,
+        Err(e) => {
             return SkillInstallResult {
                 success: false,
-                message: format!("Failed to write {}: {}", file.name, e),
+                message: format!("Failed to determine skills directory: {}", e),
                 path: None,
             };
         }
+    };
+
+    let skill_dir = base_dir.join(&skill_name);
+    if !skill_dir.is_dir() {
+        return install_skill(skill_name, files, is_global, working_directory).await;
     }
 
-    let installed_path = skill_dir.to_string_lossy().to_string();
+    let installed = SkillMetadata::from_dir(&skill_dir).version;
+    let incoming = SkillMetadata::from_files(&files).version;
+    let is_newer = match (&incoming, &installed) {
+        (Some(incoming), Some(installed)) => compare_versions(incoming, installed) == Ordering::Greater,
+        // An unversioned install is treated as older than any versioned release
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+
+    if !is_newer && !force.unwrap_or(false) {
+        return SkillInstallResult {
+            success: true,
+            message: format!(
+                "{} skill is already up to date ({})",
+                skill_name,
+                installed.as_deref().unwrap_or("unversioned")
+            ),
+            path: Some(skill_dir.to_string_lossy().to_string()),
+        };
+    }
+
+    // Write the new version beside the old one and swap it in, so files dropped
+    // from the new version don't linger and a failed write keeps the old install
+    let stamp = staging_stamp();
+    let staging = base_dir.join(format!(".{}.update-{}", skill_name, stamp));
+    let backup = base_dir.join(format!(".{}.previous-{}", skill_name, stamp));
+    let result = write_skill_files(&staging, &files).and_then(|()| swap_skill_dir(&staging, &skill_dir, &backup));
+    if let Err(e) = result {
+        let _ = std::fs::remove_dir_all(&staging);
+        return SkillInstallResult {
+            success: false,
+            message: e,
+            path: None,
+        };
+    }
 
     SkillInstallResult {
         success: true,
-        message: format!("Successfully installed {} skill", skill_name),
-        path: Some(installed_path),
+        message: format!(
+            "Successfully updated {} skill to {}",
+            skill_name,
+            incoming.as_deref().unwrap_or("the provided files")
+        ),
+        path: Some(skill_dir.to_string_lossy().to_string()),
+    }
+}
+
+/// Largest skill archive `install_skill_from_url` will download
+const MAX_SKILL_ARCHIVE_BYTES: usize = 20 * 1024 * 1024;
+const SKILL_DOWNLOAD_TIMEOUT_SECS: u64 = 60;
+
+/// Archive formats accepted by `install_skill_from_url`, sniffed from magic bytes
+enum SkillArchive {
+    Zip,
+    TarGz,
+}
+
+impl SkillArchive {
+    fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"PK\x03\x04") {
+            Some(SkillArchive::Zip)
+        } else if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(SkillArchive::TarGz)
+        } else {
+            None
+        }
+    }
+
+    /// Windows ships bsdtar, which reads zip archives too; elsewhere zip needs unzip
+    fn uses_unzip(&self) -> bool {
+        matches!(self, SkillArchive::Zip) && !cfg!(target_os = "windows")
+    }
+
+    /// Command printing one entry path per line
+    fn list_command(&self, archive: &Path) -> Command {
+        if self.uses_unzip() {
+            let mut command = Command::new("unzip");
+            command.arg("-Z1").arg(archive);
+            return command;
+        }
+        let mut command = Command::new("tar");
+        command
+            .arg(if matches!(self, SkillArchive::TarGz) { "-tzf" } else { "-tf" })
+            .arg(archive);
+        command
+    }
+
+    fn extract_command(&self, archive: &Path, dest: &Path) -> Command {
+        if self.uses_unzip() {
+            let mut command = Command::new("unzip");
+            command.arg("-q").arg(archive).arg("-d").arg(dest);
+            return command;
+        }
+        let mut command = Command::new("tar");
+        command
+            .arg(if matches!(self, SkillArchive::TarGz) { "-xzf" } else { "-xf" })
+            .arg(archive)
+            .arg("-C")
+            .arg(dest);
+        command
+    }
+}
+
+/// Archive entries must stay inside the extraction directory
+pub(crate) fn validate_archive_entry(entry: &str) -> Result<(), String> {
+    use std::path::Component;
+
+    let escapes = entry.contains('\\')
+        || Path::new(entry)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if escapes {
+        return Err(format!("Archive entry escapes the skill directory: {}", entry));
+    }
+    Ok(())
+}
+
+/// Reject symlinks in extracted content; entry names alone can't rule them out
+fn reject_symlinks(dir: &Path) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read extracted files: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to inspect {}: {}", path.display(), e))?;
+        if file_type.is_symlink() {
+            return Err(format!("Archive contains a symlink: {}", path.display()));
+        }
+        if file_type.is_dir() {
+            reject_symlinks(&path)?;
+        }
+    }
+    Ok(())
+}
+
+async fn download_skill_archive(url: &str) -> Result<Vec<u8>, String> {
+    let mut response = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", "hatch-desktop")
+        .timeout(std::time::Duration::from_secs(SKILL_DOWNLOAD_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download skill: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to download skill: HTTP {}", response.status()));
+    }
+
+    let too_large = || format!("Skill archive exceeds {} bytes", MAX_SKILL_ARCHIVE_BYTES);
+    if response.content_length().is_some_and(|len| len > MAX_SKILL_ARCHIVE_BYTES as u64) {
+        return Err(too_large());
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download skill: {}", e))?
+    {
+        if bytes.len() + chunk.len() > MAX_SKILL_ARCHIVE_BYTES {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Extract `bytes` into `staging`, returning the directory holding the skill's
+/// files. A single wrapping top-level directory (as in GitHub tarballs) is unwrapped.
+fn extract_skill_archive(bytes: &[u8], staging: &Path) -> Result<PathBuf, String> {
+    let kind = SkillArchive::detect(bytes).ok_or("Skill download is not a .zip or .tar.gz archive")?;
+
+    let archive_path = staging.join("skill-archive");
+    std::fs::write(&archive_path, bytes).map_err(|e| format!("Failed to save skill archive: {}", e))?;
+
+    let listing = kind
+        .list_command(&archive_path)
+        .output()
+        .map_err(|e| format!("Failed to read skill archive: {}", e))?;
+    if !listing.status.success() {
+        return Err(format!(
+            "Failed to read skill archive: {}",
+            String::from_utf8_lossy(&listing.stderr).trim()
+        ));
+    }
+    for entry in String::from_utf8_lossy(&listing.stdout).lines().filter(|line| !line.is_empty()) {
+        validate_archive_entry(entry)?;
+    }
+
+    let extract_dir = staging.join("extracted");
+    std::fs::create_dir_all(&extract_dir).map_err(|e| format!("Failed to create extraction directory: {}", e))?;
+    let extraction = kind
+        .extract_command(&archive_path, &extract_dir)
+        .output()
+        .map_err(|e| format!("Failed to extract skill archive: {}", e))?;
+    if !extraction.status.success() {
+        return Err(format!(
+            "Failed to extract skill archive: {}",
+            String::from_utf8_lossy(&extraction.stderr).trim()
+        ));
+    }
+    reject_symlinks(&extract_dir)?;
+
+    let top_level: Vec<PathBuf> = std::fs::read_dir(&extract_dir)
+        .map_err(|e| format!("Failed to read extracted files: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    match top_level.as_slice() {
+        [] => Err("Skill archive is empty".to_string()),
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Ok(extract_dir),
+    }
+}
+
+/// Move `source` into place at `skill_dir`, replacing any existing install
+fn swap_skill_dir(source: &Path, skill_dir: &Path, backup: &Path) -> Result<(), String> {
+    let had_previous = skill_dir.exists();
+    if had_previous {
+        std::fs::rename(skill_dir, backup).map_err(|e| format!("Failed to move previous version aside: {}", e))?;
+    }
+
+    if let Err(e) = std::fs::rename(source, skill_dir) {
+        if had_previous {
+            let _ = std::fs::rename(backup, skill_dir);
+        }
+        return Err(format!("Failed to move skill into place: {}", e));
+    }
+
+    if had_previous {
+        let _ = std::fs::remove_dir_all(backup);
+    }
+    Ok(())
+}
+
+/// Download a `.zip` or `.tar.gz` skill and install it. Entries are validated to
+/// stay within the skill directory, and files are staged in a temp directory
+/// then renamed into place so a failed install never leaves a partial skill.
+#[tauri::command]
+pub async fn install_skill_from_url(
+    skill_name: String,
+    url: String,
+    is_global: bool,
+    working_directory: Option<String>,
+) -> SkillInstallResult {
+    let failure = |message: String| SkillInstallResult {
+        success: false,
+        message,
+        path: None,
+    };
+
+    if let Err(e) = validate_skill_name(&skill_name) {
+        return failure(e);
+    }
+
+    let base_dir = match get_skills_dir(is_global, working_directory) {
+        Ok(dir) => dir,
+        Err(e) => return failure(format!("Failed to determine skills directory: {}", e)),
+    };
+
+    let bytes = match download_skill_archive(&url).await {
+        Ok(bytes) => bytes,
+        Err(e) => return failure(e),
+    };
+
+    let stamp = staging_stamp();
+    // Stage next to the target so the final rename stays on one filesystem
+    let staging = base_dir.join(format!(".{}.download-{}", skill_name, stamp));
+    let skill_dir = base_dir.join(&skill_name);
+    let backup = base_dir.join(format!(".{}.previous-{}", skill_name, stamp));
+
+    // unzip/tar and the directory walks are blocking; keep them off the async runtime
+    let install_dir = skill_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&staging).map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        let result = extract_skill_archive(&bytes, &staging)
+            .and_then(|root| swap_skill_dir(&root, &install_dir, &backup));
+        let _ = std::fs::remove_dir_all(&staging);
+        result
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("Skill install failed: {}", e)));
+
+    match result {
+        Ok(()) => SkillInstallResult {
+            success: true,
+            message: format!("Successfully installed {} skill", skill_name),
+            path: Some(skill_dir.to_string_lossy().to_string()),
+        },
+        Err(e) => failure(e),
     }
 }
 
@@ -105,6 +561,14 @@ pub async fn uninstall_skill(
     is_global: bool,
     working_directory: Option<String>,
 ) -> SkillInstallResult {
+    if let Err(e) = validate_skill_name(&skill_name) {
+        return SkillInstallResult {
+            success: false,
+            message: e,
+            path: None,
+        };
+    }
+
     let base_dir = match get_skills_dir(is_global, working_directory) {
         Ok(dir) => dir,
         Err(e) => {
@@ -141,12 +605,12 @@ pub async fn uninstall_skill(
     }
 }
 
-/// List installed skills
+/// List installed skills with their declared version and description
 #[tauri::command]
 pub async fn list_installed_skills(
     is_global: bool,
     working_directory: Option<String>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<InstalledSkill>, String> {
     let base_dir = get_skills_dir(is_global, working_directory)?;
 
     if !base_dir.exists() {
@@ -162,7 +626,13 @@ pub async fn list_installed_skills(
             let path = entry.path();
             if path.is_dir() {
                 if let Some(name) = path.file_name() {
-                    skills.push(name.to_string_lossy().to_string());
+                    let metadata = SkillMetadata::from_dir(&path);
+                    skills.push(InstalledSkill {
+                        name: name.to_string_lossy().to_string(),
+                        version: metadata.version,
+                        description: metadata.description,
+                        path: path.to_string_lossy().to_string(),
+                    });
                 }
             }
         }
@@ -199,78 +669,265 @@ pub async fn get_skill_install_path(
     Ok(skill_dir.to_string_lossy().to_string())
 }
 
-/// Run a shell command (used for npx claude-code-templates install)
-#[tauri::command]
-pub async fn run_shell_command(
-    command: String,
+/// Directory a shell command runs in: the given one or the current directory
+fn shell_working_directory(working_directory: Option<String>) -> Result<PathBuf, String> {
+    match working_directory {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e)),
+    }
+}
+
+/// Try to extract the installation path from command output
+fn install_path_from_output(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find(|line| line.contains(".claude/") || line.contains("installed"))
+        .map(|s| s.to_string())
+}
+
+/// Shell commands started with a session id, so `cancel_shell_command` can stop them
+#[derive(Clone, Default)]
+pub struct ShellProcessRegistry {
+    /// Session id -> pid of the process group leader
+    processes: Arc<tokio::sync::Mutex<HashMap<String, u32>>>,
+}
+
+impl ShellProcessRegistry {
+    async fn register(&self, session_id: &str, pid: u32) {
+        self.processes.lock().await.insert(session_id.to_string(), pid);
+    }
+
+    /// Remove a session, returning its pid if it hadn't been cancelled
+    async fn take(&self, session_id: &str) -> Option<u32> {
+        self.processes.lock().await.remove(session_id)
+    }
+}
+
+/// Kill a shell and everything it started. Commands are spawned as their own
+/// process group, so npx/node grandchildren go down with the shell.
+fn kill_process_tree(pid: u32) {
+    if cfg!(target_os = "windows") {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .output();
+    } else {
+        let _ = Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", pid)])
+            .output();
+    }
+}
+
+/// Spawn `command` in a shell, collecting its output and optionally emitting each
+/// line as a `shell-stream` event. Stops the whole process tree on timeout or
+/// when the session is cancelled.
+async fn run_shell_process(
+    command: &str,
     working_directory: Option<String>,
+    env: Option<HashMap<String, String>>,
+    session_id: Option<&str>,
+    timeout_secs: Option<u64>,
+    registry: &ShellProcessRegistry,
+    stream: Option<&tauri::AppHandle>,
 ) -> SkillInstallResult {
-    // Get the working directory
-    let cwd = if let Some(dir) = working_directory {
-        PathBuf::from(dir)
-    } else {
-        match std::env::current_dir() {
-            Ok(dir) => dir,
-            Err(e) => {
-                return SkillInstallResult {
-                    success: false,
-                    message: format!("Failed to get current directory: {}", e),
-                    path: None,
-                };
-            }
+    let emit = |app: &tauri::AppHandle, event_type: &str, data: String, session_id: &str| {
+        let _ = app.emit(
+            "shell-stream",
+            StreamEvent {
+                event_type: event_type.to_string(),
+                data,
+                session_id: session_id.to_string(),
+                plan: false,
+            },
+        );
+    };
+    let stream_session = session_id.unwrap_or_default().to_string();
+    let finish = |result: SkillInstallResult, exit_code: Option<i32>| {
+        if let Some(app) = stream {
+            emit(
+                app,
+                "done",
+                exit_code.map(|code| code.to_string()).unwrap_or_default(),
+                &stream_session,
+            );
         }
+        result
+    };
+    let failure = |message: String| SkillInstallResult {
+        success: false,
+        message,
+        path: None,
     };
 
-    // Run the command
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", &command])
-            .current_dir(&cwd)
-            .output()
+    let cwd = match shell_working_directory(working_directory) {
+        Ok(dir) => dir,
+        Err(e) => return finish(failure(e), None),
+    };
+    let env = match resolve_env(env) {
+        Ok(env) => env,
+        Err(e) => return finish(failure(format!("Failed to resolve environment: {}", e)), None),
+    };
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = AsyncCommand::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
     } else {
-        Command::new("sh")
-            .args(["-c", &command])
-            .current_dir(&cwd)
-            .output()
+        let mut cmd = AsyncCommand::new("sh");
+        cmd.args(["-c", command]);
+        cmd
     };
+    cmd.current_dir(&cwd)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
 
-    match output {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-
-            if output.status.success() {
-                // Try to extract the installation path from output
-                let path = stdout
-                    .lines()
-                    .find(|line| line.contains(".claude/") || line.contains("installed"))
-                    .map(|s| s.to_string());
-
-                SkillInstallResult {
-                    success: true,
-                    message: if stdout.is_empty() {
-                        "Command completed successfully".to_string()
-                    } else {
-                        stdout.to_string()
-                    },
-                    path,
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return finish(failure(format!("Failed to execute command: {}", e)), None),
+    };
+    let pid = child.id();
+    if let (Some(session_id), Some(pid)) = (session_id, pid) {
+        registry.register(session_id, pid).await;
+    }
+
+    let stdout = child.stdout.take().expect("Failed to get stdout");
+    let stderr = child.stderr.take().expect("Failed to get stderr");
+    let collect = |pipe: Box<dyn tokio::io::AsyncRead + Send + Unpin>, event_type: &'static str| {
+        let app = stream.cloned();
+        let session_id = stream_session.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(pipe).lines();
+            let mut full = String::new();
+            while let Ok(Some(line)) = reader.next_line().await {
+                full.push_str(&line);
+                full.push('\n');
+                if let Some(app) = &app {
+                    emit(app, event_type, line, &session_id);
                 }
-            } else {
-                SkillInstallResult {
-                    success: false,
-                    message: if stderr.is_empty() {
-                        format!("Command failed with exit code: {:?}", output.status.code())
-                    } else {
-                        stderr.to_string()
-                    },
-                    path: None,
+            }
+            full
+        })
+    };
+    let stdout_handle = collect(Box::new(stdout), "line");
+    let stderr_handle = collect(Box::new(stderr), "stderr");
+
+    let (status, timed_out) = match timeout_secs {
+        Some(secs) => match tokio::time::timeout(std::time::Duration::from_secs(secs), child.wait()).await {
+            Ok(status) => (status, false),
+            Err(_) => {
+                if let Some(pid) = pid {
+                    kill_process_tree(pid);
                 }
+                (child.wait().await, true)
             }
-        }
-        Err(e) => SkillInstallResult {
-            success: false,
-            message: format!("Failed to execute command: {}", e),
-            path: None,
         },
-    }
+        None => (child.wait().await, false),
+    };
+
+    let cancelled = match session_id {
+        Some(session_id) => registry.take(session_id).await.is_none(),
+        None => false,
+    };
+    let full_stdout = stdout_handle.await.unwrap_or_default();
+    let full_stderr = stderr_handle.await.unwrap_or_default();
+    let exit_code = status.as_ref().ok().and_then(|status| status.code());
+
+    let result = if timed_out {
+        failure(format!(
+            "Command timed out after {}s and was terminated",
+            timeout_secs.unwrap_or_default()
+        ))
+    } else if cancelled {
+        failure("Command was cancelled".to_string())
+    } else {
+        match status {
+            Ok(status) if status.success() => SkillInstallResult {
+                success: true,
+                message: if full_stdout.is_empty() {
+                    "Command completed successfully".to_string()
+                } else {
+                    full_stdout.clone()
+                },
+                path: install_path_from_output(&full_stdout),
+            },
+            Ok(status) => failure(if full_stderr.is_empty() {
+                format!("Command failed with exit code: {:?}", status.code())
+            } else {
+                full_stderr
+            }),
+            Err(e) => failure(format!("Failed to wait for command: {}", e)),
+        }
+    };
+
+    finish(result, exit_code)
+}
+
+/// Run a shell command (used for npx claude-code-templates install).
+/// `timeout_secs` kills the command if it runs too long, e.g. stuck on a prompt;
+/// `session_id` makes it cancellable via `cancel_shell_command`. `env` adds
+/// variables; values like `keychain:OPENAI_API_KEY` are read from the keychain.
+#[tauri::command]
+pub async fn run_shell_command(
+    registry: State<'_, ShellProcessRegistry>,
+    command: String,
+    working_directory: Option<String>,
+    session_id: Option<String>,
+    timeout_secs: Option<u64>,
+    env: Option<HashMap<String, String>>,
+) -> Result<SkillInstallResult, String> {
+    Ok(run_shell_process(
+        &command,
+        working_directory,
+        env,
+        session_id.as_deref(),
+        timeout_secs,
+        &registry,
+        None,
+    )
+    .await)
+}
+
+/// Run a shell command, emitting each output line as a `shell-stream` event
+/// ("line" for stdout, "stderr" for stderr) and a final "done" carrying the
+/// exit code, so slow installs show live progress
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn run_shell_command_streaming(
+    app: tauri::AppHandle,
+    registry: State<'_, ShellProcessRegistry>,
+    command: String,
+    working_directory: Option<String>,
+    sessionId: String,
+    timeout_secs: Option<u64>,
+    env: Option<HashMap<String, String>>,
+) -> Result<SkillInstallResult, String> {
+    Ok(run_shell_process(
+        &command,
+        working_directory,
+        env,
+        Some(&sessionId),
+        timeout_secs,
+        &registry,
+        Some(&app),
+    )
+    .await)
+}
+
+/// Stop a running shell command and every process it started
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn cancel_shell_command(
+    registry: State<'_, ShellProcessRegistry>,
+    sessionId: String,
+) -> Result<(), String> {
+    let pid = registry
+        .take(&sessionId)
+        .await
+        .ok_or_else(|| format!("No running shell command for session {}", sessionId))?;
+    kill_process_tree(pid);
+    Ok(())
 }
@@ -0,0 +1,277 @@
+//! Agent session transcripts, stored as one JSONL file per session under
+//! `~/.hatch/sessions` so a workspace's prior agent output survives restarts.
+//! The first line is a `session` header; every later line is a stream event.
+
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+const SESSIONS_DIR: &str = ".hatch/sessions";
+const HEADER_TYPE: &str = "session";
+
+/// A recorded stream event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: String,
+    pub timestamp: u64,
+}
+
+/// First line of a transcript, tying the session to its agent and workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TranscriptHeader {
+    #[serde(rename = "type")]
+    header_type: String,
+    agent: String,
+    workspace_id: Option<String>,
+    timestamp: u64,
+}
+
+/// A stored session as listed for a workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub agent: String,
+    pub workspace_id: Option<String>,
+    pub started_at: u64,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn sessions_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join(SESSIONS_DIR))
+}
+
+/// Session ids come from the frontend and become file names, so only allow
+/// characters that can't form a path
+pub(crate) fn transcript_path(dir: &Path, session_id: &str) -> Result<PathBuf, String> {
+    let valid = !session_id.is_empty()
+        && session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+    Ok(dir.join(format!("{}.jsonl", session_id)))
+}
+
+/// A line queued for the writer thread
+enum TranscriptLine {
+    Header {
+        session_id: String,
+        header: TranscriptHeader,
+    },
+    Event {
+        session_id: String,
+        event: TranscriptEvent,
+    },
+}
+
+impl TranscriptLine {
+    fn session_id(&self) -> &str {
+        match self {
+            TranscriptLine::Header { session_id, .. } | TranscriptLine::Event { session_id, .. } => session_id,
+        }
+    }
+}
+
+/// Payload of the `transcript-error` event
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptErrorEvent {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// Appends transcript lines on a background thread, so recording never blocks
+/// the async stream readers. Lines queued while a batch is written are appended
+/// together, one open per session.
+pub struct TranscriptWriter {
+    sender: Mutex<mpsc::Sender<TranscriptLine>>,
+}
+
+impl TranscriptWriter {
+    pub fn new(app_handle: Option<AppHandle>) -> Self {
+        Self::in_dir(sessions_dir(), app_handle)
+    }
+
+    pub(crate) fn in_dir(dir: Result<PathBuf, String>, app_handle: Option<AppHandle>) -> Self {
+        let (sender, receiver) = mpsc::channel::<TranscriptLine>();
+        std::thread::spawn(move || {
+            while let Ok(first) = receiver.recv() {
+                let batch: Vec<TranscriptLine> = std::iter::once(first).chain(receiver.try_iter()).collect();
+                let failures = match &dir {
+                    Ok(dir) => append_batch(dir, batch),
+                    Err(e) => batch.iter().map(|line| (line.session_id().to_string(), e.clone())).collect(),
+                };
+                if let Some(app) = &app_handle {
+                    for (session_id, error) in failures {
+                        let _ = app.emit("transcript-error", TranscriptErrorEvent { session_id, error });
+                    }
+                }
+            }
+        });
+        Self {
+            sender: Mutex::new(sender),
+        }
+    }
+
+    fn send(&self, line: TranscriptLine) {
+        if let Ok(sender) = self.sender.lock() {
+            let _ = sender.send(line);
+        }
+    }
+
+    /// Start recording a session. A session that already has a transcript keeps its header.
+    pub fn start_session(&self, session_id: &str, agent: &str, workspace_id: Option<&str>) {
+        self.send(TranscriptLine::Header {
+            session_id: session_id.to_string(),
+            header: TranscriptHeader {
+                header_type: HEADER_TYPE.to_string(),
+                agent: agent.to_string(),
+                workspace_id: workspace_id.map(|id| id.to_string()),
+                timestamp: now_ms(),
+            },
+        });
+    }
+
+    /// Queue an event for a session's transcript. Write failures are reported as
+    /// `transcript-error` events rather than returned, so a full disk never
+    /// interrupts an agent run.
+    pub fn record_event(&self, session_id: &str, event_type: &str, data: &str) {
+        self.send(TranscriptLine::Event {
+            session_id: session_id.to_string(),
+            event: TranscriptEvent {
+                event_type: event_type.to_string(),
+                data: data.to_string(),
+                timestamp: now_ms(),
+            },
+        });
+    }
+}
+
+/// Append a batch of lines, grouped per session. Returns the sessions that
+/// failed and why.
+fn append_batch(dir: &Path, batch: Vec<TranscriptLine>) -> Vec<(String, String)> {
+    let mut sessions: Vec<(String, Vec<TranscriptLine>)> = Vec::new();
+    for line in batch {
+        match sessions.iter_mut().find(|(session_id, _)| session_id == line.session_id()) {
+            Some((_, lines)) => lines.push(line),
+            None => sessions.push((line.session_id().to_string(), vec![line])),
+        }
+    }
+
+    sessions
+        .into_iter()
+        .filter_map(|(session_id, lines)| {
+            append_lines(dir, &session_id, lines).err().map(|e| (session_id, e))
+        })
+        .collect()
+}
+
+fn append_lines(dir: &Path, session_id: &str, lines: Vec<TranscriptLine>) -> Result<(), String> {
+    let path = transcript_path(dir, session_id)?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create sessions directory: {}", e))?;
+
+    let mut exists = path.exists();
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open transcript: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    for line in lines {
+        let json = match line {
+            TranscriptLine::Header { header, .. } => {
+                if exists {
+                    continue;
+                }
+                serde_json::to_string(&header)
+            }
+            TranscriptLine::Event { event, .. } => serde_json::to_string(&event),
+        }
+        .map_err(|e| format!("Failed to serialize transcript entry: {}", e))?;
+        writeln!(writer, "{}", json).map_err(|e| format!("Failed to write transcript: {}", e))?;
+        exists = true;
+    }
+    writer.flush().map_err(|e| format!("Failed to write transcript: {}", e))
+}
+
+fn read_header(path: &Path) -> Option<TranscriptHeader> {
+    let file = std::fs::File::open(path).ok()?;
+    let first_line = BufReader::new(file).lines().next()?.ok()?;
+    serde_json::from_str::<TranscriptHeader>(&first_line)
+        .ok()
+        .filter(|header| header.header_type == HEADER_TYPE)
+}
+
+pub(crate) fn read_transcript(dir: &Path, session_id: &str) -> Result<Vec<TranscriptEvent>, String> {
+    let path = transcript_path(dir, session_id)?;
+    let contents = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    // Skip the header and any line cut short by a crash mid-write
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<TranscriptEvent>(line).ok())
+        .collect())
+}
+
+pub(crate) fn list_sessions_in(dir: &Path, workspace_id: &str) -> Result<Vec<SessionSummary>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read sessions directory: {}", e))?;
+    let mut sessions: Vec<SessionSummary> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let session_id = path.file_name()?.to_str()?.strip_suffix(".jsonl")?.to_string();
+            let header = read_header(&path)?;
+            if header.workspace_id.as_deref() != Some(workspace_id) {
+                return None;
+            }
+            Some(SessionSummary {
+                session_id,
+                agent: header.agent,
+                workspace_id: header.workspace_id,
+                started_at: header.timestamp,
+            })
+        })
+        .collect();
+
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+    Ok(sessions)
+}
+
+/// Read back a session's recorded events, oldest first
+#[tauri::command]
+pub fn get_session_transcript(session_id: String) -> Result<Vec<TranscriptEvent>, String> {
+    read_transcript(&sessions_dir()?, &session_id)
+}
+
+/// List the sessions recorded for a workspace, newest first
+#[tauri::command]
+pub fn list_sessions(workspace_id: String) -> Result<Vec<SessionSummary>, String> {
+    list_sessions_in(&sessions_dir()?, &workspace_id)
+}
+
+/// Delete a session's transcript and its in-memory output tail. Succeeds if
+/// it's already gone.
+#[tauri::command]
+pub fn clear_session(tails: tauri::State<'_, crate::AgentLogTails>, session_id: String) -> Result<(), String> {
+    tails.clear(&session_id);
+    let path = transcript_path(&sessions_dir()?, &session_id)?;
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to delete transcript: {}", e)),
+    }
+}
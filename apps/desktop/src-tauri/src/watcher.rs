@@ -0,0 +1,211 @@
+//! File watchers that tell the frontend when a workspace's files change, so the
+//! file tree and diff views follow an agent's edits without a manual refresh.
+//! Each watcher is keyed by a caller-chosen session id and emits debounced
+//! `repo-changed` events until it's unwatched. Watch failures (e.g. an event
+//! queue overflow) are reported as `repo-watch-error` events.
+//!
+//! Directories are watched one by one rather than recursively from the root,
+//! so ignored trees like `node_modules` never use up inotify watches.
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tauri::Emitter;
+
+/// Changes within this window are reported as one event
+const DEBOUNCE_MS: u64 = 300;
+
+/// Directories whose churn never affects the file tree or diff views
+const IGNORED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Payload of the `repo-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoChangedEvent {
+    pub session_id: String,
+    pub repo_path: String,
+    /// Changed paths relative to the repo, `/`-separated and sorted
+    pub paths: Vec<String>,
+}
+
+/// Payload of the `repo-watch-error` event. Changes may have been missed, so
+/// listeners should re-read whatever they display.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoWatchErrorEvent {
+    pub session_id: String,
+    pub repo_path: String,
+    pub error: String,
+}
+
+/// Active watchers by session id. Dropping a watcher stops it.
+#[derive(Default)]
+pub struct RepoWatchers {
+    watchers: Mutex<HashMap<String, Arc<Mutex<TreeWatcher>>>>,
+}
+
+/// A debounced watcher over a repo's directories, each watched non-recursively
+struct TreeWatcher {
+    debouncer: Debouncer<RecommendedWatcher>,
+    watched: HashSet<PathBuf>,
+}
+
+impl TreeWatcher {
+    /// Watch `dir` and every directory below it that `watchable_dirs` keeps
+    fn watch_tree(&mut self, dir: &Path, worktrees_dir: &Path) -> Result<(), String> {
+        for dir in watchable_dirs(dir, worktrees_dir) {
+            if self.watched.contains(&dir) {
+                continue;
+            }
+            self.debouncer
+                .watcher()
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+            self.watched.insert(dir);
+        }
+        Ok(())
+    }
+}
+
+fn is_ignored_dir(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| IGNORED_DIRS.iter().any(|dir| name == *dir))
+        .unwrap_or(false)
+}
+
+/// `dir` and the directories below it, minus ignored directories, the repo's
+/// workspace worktrees and symlinks
+pub(crate) fn watchable_dirs(dir: &Path, worktrees_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        if is_ignored_dir(&dir) || dir.starts_with(worktrees_dir) {
+            continue;
+        }
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                if entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false) {
+                    stack.push(entry.path());
+                }
+            }
+        }
+        dirs.push(dir);
+    }
+    dirs
+}
+
+/// Relative paths of the changes worth reporting, skipping ignored directories
+/// and the repo's own workspace worktrees, which have their own watchers
+pub(crate) fn relevant_changes(root: &Path, worktrees_dir: &Path, paths: &[PathBuf]) -> Vec<String> {
+    let changed: BTreeSet<String> = paths
+        .iter()
+        .filter(|path| !path.starts_with(worktrees_dir))
+        .filter_map(|path| path.strip_prefix(root).ok())
+        .filter(|relative| {
+            !relative
+                .components()
+                .any(|component| IGNORED_DIRS.iter().any(|dir| component.as_os_str() == *dir))
+        })
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .filter(|relative| !relative.is_empty())
+        .collect();
+    changed.into_iter().collect()
+}
+
+/// Start emitting `repo-changed` events for files under `repo_path`. Watching
+/// again with the same session id replaces the previous watcher.
+#[tauri::command]
+pub fn watch_repo(
+    app: tauri::AppHandle,
+    watchers: tauri::State<'_, RepoWatchers>,
+    repo_path: String,
+    session_id: String,
+) -> Result<(), String> {
+    let root = PathBuf::from(&repo_path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to watch {}: {}", repo_path, e))?;
+    if !root.is_dir() {
+        return Err(format!("Directory does not exist: {}", repo_path));
+    }
+    let worktrees_dir = crate::git::get_worktrees_dir(&root);
+
+    let event_session_id = session_id.clone();
+    let event_root = root.clone();
+    let event_worktrees_dir = worktrees_dir.clone();
+    // The handler adds watches for new directories; a weak reference keeps it
+    // from holding the watcher alive after it's unwatched
+    let handler_watcher: Arc<Mutex<Weak<Mutex<TreeWatcher>>>> = Arc::default();
+    let tree_watcher_slot = handler_watcher.clone();
+    let debouncer = new_debouncer(Duration::from_millis(DEBOUNCE_MS), move |result: DebounceEventResult| {
+        let emit_error = |error: String| {
+            let _ = app.emit(
+                "repo-watch-error",
+                RepoWatchErrorEvent {
+                    session_id: event_session_id.clone(),
+                    repo_path: repo_path.clone(),
+                    error,
+                },
+            );
+        };
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => {
+                emit_error(e.to_string());
+                return;
+            }
+        };
+        let paths: Vec<PathBuf> = events.into_iter().map(|event| event.path).collect();
+
+        let tree_watcher = handler_watcher.lock().ok().and_then(|watcher| watcher.upgrade());
+        if let Some(tree_watcher) = tree_watcher {
+            if let Ok(mut tree_watcher) = tree_watcher.lock() {
+                tree_watcher.watched.retain(|dir| dir.is_dir());
+                for path in paths.iter().filter(|path| path.is_dir()) {
+                    if let Err(e) = tree_watcher.watch_tree(path, &event_worktrees_dir) {
+                        emit_error(e);
+                    }
+                }
+            }
+        }
+
+        let changed = relevant_changes(&event_root, &event_worktrees_dir, &paths);
+        if changed.is_empty() {
+            return;
+        }
+        let _ = app.emit(
+            "repo-changed",
+            RepoChangedEvent {
+                session_id: event_session_id.clone(),
+                repo_path: repo_path.clone(),
+                paths: changed,
+            },
+        );
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    let tree_watcher = Arc::new(Mutex::new(TreeWatcher {
+        debouncer,
+        watched: HashSet::new(),
+    }));
+    tree_watcher
+        .lock()
+        .map_err(|_| "File watcher is poisoned")?
+        .watch_tree(&root, &worktrees_dir)?;
+    if let Ok(mut slot) = tree_watcher_slot.lock() {
+        *slot = Arc::downgrade(&tree_watcher);
+    }
+
+    let mut watchers = watchers.watchers.lock().map_err(|_| "File watcher registry is poisoned")?;
+    watchers.insert(session_id, tree_watcher);
+    Ok(())
+}
+
+/// Stop the watcher for a session. Succeeds if it isn't watching.
+#[tauri::command]
+pub fn unwatch_repo(watchers: tauri::State<'_, RepoWatchers>, session_id: String) -> Result<(), String> {
+    let mut watchers = watchers.watchers.lock().map_err(|_| "File watcher registry is poisoned")?;
+    watchers.remove(&session_id);
+    Ok(())
+}
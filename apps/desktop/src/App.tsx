@@ -2,18 +2,16 @@ import { useEffect } from 'react'
 import { BrowserRouter, Routes, Route } from 'react-router-dom'
 import { Layout } from './components/layout/Layout'
 import { IDEPage } from './pages/IDEPage'
-import { useSettingsStore, LOCAL_AGENT_IDS } from './stores/settingsStore'
+import { useSettingsStore } from './stores/settingsStore'
 import { ToastContainer } from './components/ui/ToastContainer'
 
 function App() {
-  const { checkAgentStatus } = useSettingsStore()
+  const { checkAllAgentStatuses } = useSettingsStore()
 
   // Check all local agents on startup (in background)
   useEffect(() => {
-    for (const agentId of LOCAL_AGENT_IDS) {
-      checkAgentStatus(agentId)
-    }
-  }, [checkAgentStatus])
+    checkAllAgentStatuses()
+  }, [checkAllAgentStatuses])
 
   return (
     <>
@@ -1,7 +1,7 @@
 import { useEffect, useState, useCallback } from 'react'
 import { useSettingsStore, LOCAL_AGENT_IDS } from '../stores/settingsStore'
 import type { AgentStatus, LocalAgentId, ModelInfo } from '../lib/agents/types'
-import type { BranchNamePrefix as _BranchNamePrefix } from '../stores/settingsStore'
+import type { BranchNamePrefix as _BranchNamePrefix, ModelSelectableAgent } from '../stores/settingsStore'
 import { AGENT_CONFIGS } from '../lib/agents/registry'
 import {
   CheckCircle2,
@@ -360,7 +360,7 @@ function AgentModelSelector({
   isLoading,
   onChange,
 }: {
-  agent: 'opencode' | 'cursor'
+  agent: ModelSelectableAgent
   currentModel: string
   availableModels: ModelInfo[]
   isLoading: boolean
@@ -375,7 +375,7 @@ function AgentModelSelector({
     })),
   ]
 
-  const agentName = agent === 'opencode' ? 'Opencode' : 'Cursor'
+  const agentName = { 'claude-code': 'Claude Code', opencode: 'Opencode', cursor: 'Cursor' }[agent]
 
   return (
     <div className="flex items-center justify-between py-3 border-b border-white/5 last:border-b-0">
@@ -426,7 +426,11 @@ function AgentsSettings() {
     if (!modelsFetched) {
       const fetchModels = async () => {
         // Always try to fetch - the Rust side handles the case where agent isn't installed
-        await Promise.all([fetchAgentModels('opencode'), fetchAgentModels('cursor')])
+        await Promise.all([
+          fetchAgentModels('claude-code'),
+          fetchAgentModels('opencode'),
+          fetchAgentModels('cursor'),
+        ])
         setModelsFetched(true)
       }
       fetchModels()
@@ -435,7 +439,7 @@ function AgentsSettings() {
 
   // Refetch models when agent becomes installed
   const handleRefetchModels = useCallback(
-    async (agent: 'opencode' | 'cursor') => {
+    async (agent: ModelSelectableAgent) => {
       await fetchAgentModels(agent)
     },
     [fetchAgentModels]
@@ -461,7 +465,7 @@ function AgentsSettings() {
               onCheck={() => {
                 checkAgentStatus(agentId)
                 // Refetch models after status check for applicable agents
-                if (agentId === 'opencode' || agentId === 'cursor') {
+                if (agentId === 'claude-code' || agentId === 'opencode' || agentId === 'cursor') {
                   setTimeout(() => handleRefetchModels(agentId), 500)
                 }
               }}
@@ -490,6 +494,13 @@ function AgentsSettings() {
           Choose which model each agent should use. Models are fetched from the installed agents.
         </div>
         <div className="space-y-0">
+          <AgentModelSelector
+            agent="claude-code"
+            currentModel={agentModels['claude-code'] ?? 'default'}
+            availableModels={availableModels['claude-code']}
+            isLoading={isLoadingModels && availableModels['claude-code'].length === 0}
+            onChange={(model) => setAgentModel('claude-code', model)}
+          />
           <AgentModelSelector
             agent="opencode"
             currentModel={agentModels.opencode}
@@ -29,6 +29,7 @@ import {
   Palette,
 } from 'lucide-react'
 import { keychainSet } from '../../lib/keychain'
+import { openExternal } from '../../lib/external'
 import { CreatePRModal } from '../repository/CreatePRModal'
 import { ErrorBoundary } from '../ErrorBoundary'
 import { OnboardingWizard } from '../onboarding/OnboardingWizard'
@@ -240,9 +241,7 @@ export function Layout() {
 
                   {/* Review Button */}
                   <button
-                    onClick={() =>
-                      currentWorkspace.prUrl && window.open(currentWorkspace.prUrl, '_blank')
-                    }
+                    onClick={() => currentWorkspace.prUrl && openExternal(currentWorkspace.prUrl)}
                     className="flex items-center gap-1 px-3 py-1.5 rounded-lg border border-white/20 text-white text-xs hover:bg-white/10 transition-colors"
                   >
                     <ExternalLink size={12} />
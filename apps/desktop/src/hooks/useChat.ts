@@ -279,13 +279,15 @@ export function useChat(workspaceId?: string) {
         // Get the adapter and send message
         const adapter = getLocalAdapter(agentId)
 
-        // Get model configuration for opencode and cursor agents
+        // Get model configuration for agents that support model selection
         const model =
-          agentId === 'opencode'
-            ? agentModels.opencode
-            : agentId === 'cursor'
-              ? agentModels.cursor
-              : undefined
+          agentId === 'claude-code'
+            ? agentModels['claude-code']
+            : agentId === 'opencode'
+              ? agentModels.opencode
+              : agentId === 'cursor'
+                ? agentModels.cursor
+                : undefined
 
         // Get workspace path for agent working directory
         const workingDirectory = targetWorkspacePath
@@ -331,6 +333,7 @@ export function useChat(workspaceId?: string) {
           onStream,
           model,
           workingDirectory,
+          workspaceId: targetWorkspaceId ?? undefined,
         })
 
         void sendPromise.catch(() => undefined)
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback, useRef } from 'react'
 import { listDirectoryFiles, type FileEntry } from '../lib/git/bridge'
+import { onRepoChanged, onRepoWatchError, unwatchRepo, watchRepo } from '../lib/repoWatcher'
 
 interface UseFileTreeReturn {
   tree: FileEntry[]
@@ -19,9 +20,11 @@ export function useFileTree(workspacePath: string | undefined): UseFileTreeRetur
   const [expandedPaths, setExpandedPaths] = useState<Set<string>>(new Set())
   const [selectedPath, setSelectedPath] = useState<string | null>(null)
   const currentPathRef = useRef(workspacePath)
+  const watchSessionRef = useRef(`file-tree-${crypto.randomUUID()}`)
 
-  const fetchTree = useCallback(async (path: string) => {
-    setIsLoading(true)
+  // Background refreshes keep the current tree on screen instead of showing a spinner
+  const fetchTree = useCallback(async (path: string, background = false) => {
+    if (!background) setIsLoading(true)
     setError(null)
     try {
       const entries = await listDirectoryFiles(path, 10, false)
@@ -58,6 +61,40 @@ export function useFileTree(workspacePath: string | undefined): UseFileTreeRetur
     }
   }, [workspacePath, fetchTree])
 
+  // Re-fetch when files change on disk, e.g. while an agent edits them
+  useEffect(() => {
+    if (!workspacePath) return
+    const sessionId = watchSessionRef.current
+    const unlisteners: (() => void)[] = []
+    let disposed = false
+    const track = (fn: () => void) => {
+      if (disposed) fn()
+      else unlisteners.push(fn)
+    }
+
+    onRepoChanged(sessionId, () => {
+      if (currentPathRef.current === workspacePath) {
+        fetchTree(workspacePath, true)
+      }
+    }).then(track)
+    // Changes may have been dropped, so re-read the whole tree
+    onRepoWatchError(sessionId, (event) => {
+      console.warn('[useFileTree] Workspace watcher failed:', event.error)
+      if (currentPathRef.current === workspacePath) {
+        fetchTree(workspacePath, true)
+      }
+    }).then(track)
+    watchRepo(workspacePath, sessionId).catch((err) => {
+      console.warn('[useFileTree] Failed to watch workspace:', err)
+    })
+
+    return () => {
+      disposed = true
+      unlisteners.forEach((fn) => fn())
+      unwatchRepo(sessionId).catch(() => {})
+    }
+  }, [workspacePath, fetchTree])
+
   const toggleExpanded = useCallback((path: string) => {
     setExpandedPaths(prev => {
       const next = new Set(prev)
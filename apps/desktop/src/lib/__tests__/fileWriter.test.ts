@@ -38,6 +38,7 @@ describe('writeCodeBlocksToWorkspace', () => {
           content: 'export const add = (a: number, b: number) => a + b',
         },
       ],
+      overwrite: true,
     })
     expect(result).toEqual([
       { path: 'src/App.tsx', size: 42 },
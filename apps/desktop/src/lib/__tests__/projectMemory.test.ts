@@ -62,6 +62,7 @@ describe('projectMemory', () => {
       expect(invokeMock).toHaveBeenCalledWith('write_project_files', {
         baseDir: '/path/to/workspace',
         files: [{ path: '.hatch/context.md', content }],
+        overwrite: true,
       })
     })
 
@@ -78,6 +79,7 @@ describe('projectMemory', () => {
       expect(invokeMock).toHaveBeenCalledWith('write_project_files', {
         baseDir: '/path/to/workspace',
         files: [{ path: '.hatch/context.md', content }],
+        overwrite: true,
       })
     })
 
@@ -141,6 +143,7 @@ describe('projectMemory', () => {
             content: expect.stringContaining('# Workspace Memory'),
           },
         ],
+        overwrite: true,
       })
     })
 
@@ -23,6 +23,7 @@ interface TauriStreamEvent {
   type: string
   data: string
   session_id: string
+  plan?: boolean
 }
 
 const config: AgentConfig = {
@@ -206,7 +207,7 @@ export const claudeCodeAdapter: AgentAdapter = {
   },
 
   async sendMessage(messages: AgentMessage[], options?: SendMessageOptions): Promise<string> {
-    const { systemPrompt, onStream, workingDirectory } = options || {}
+    const { systemPrompt, onStream, model, workingDirectory, extraArgs, planMode, workspaceId } = options || {}
     const prompt = buildPromptFromMessages(messages, systemPrompt)
     const sessionId = crypto.randomUUID()
 
@@ -243,20 +244,22 @@ export const claudeCodeAdapter: AgentAdapter = {
               }
             }
           }
-          onStream?.({ type: 'done' })
+          onStream?.({ type: 'done', plan: payload.plan })
         } else if (payload.type === 'error' && payload.data) {
           onStream?.({ type: 'error', content: payload.data })
         }
       })
 
       // Invoke the streaming command
-      // Note: Claude Code doesn't support model selection - it uses its own model
       const result = await invoke<CommandResult>('run_claude_code_streaming', {
         prompt,
         sessionId,
-        planMode: false,
+        planMode: planMode ?? false,
         thinkingEnabled: true,
         workingDirectory: workingDirectory || null,
+        extraArgs: extraArgs ?? null,
+        workspaceId: workspaceId ?? null,
+        model: model || null,
       })
 
       // Clean up listener
@@ -149,7 +149,7 @@ export const codexAdapter: AgentAdapter = {
   },
 
   async sendMessage(messages: AgentMessage[], options?: SendMessageOptions): Promise<string> {
-    const { systemPrompt, onStream, workingDirectory } = options || {}
+    const { systemPrompt, onStream, workingDirectory, extraArgs, workspaceId } = options || {}
     const prompt = buildPromptFromMessages(messages, systemPrompt)
     const sessionId = crypto.randomUUID()
 
@@ -196,6 +196,8 @@ export const codexAdapter: AgentAdapter = {
         prompt,
         sessionId,
         workingDirectory: workingDirectory || null,
+        extraArgs: extraArgs ?? null,
+        workspaceId: workspaceId ?? null,
       })
 
       if (unlisten) {
@@ -225,7 +225,7 @@ export const cursorAdapter: AgentAdapter = {
   },
 
   async sendMessage(messages: AgentMessage[], options?: SendMessageOptions): Promise<string> {
-    const { systemPrompt, onStream, model, workingDirectory } = options || {}
+    const { systemPrompt, onStream, model, workingDirectory, extraArgs, planMode } = options || {}
     const prompt = buildPromptFromMessages(messages, systemPrompt)
 
     try {
@@ -234,6 +234,8 @@ export const cursorAdapter: AgentAdapter = {
         prompt,
         model: model || null,
         workingDirectory: workingDirectory || null,
+        extraArgs: extraArgs ?? null,
+        planMode: planMode ?? false,
       })
 
       if (!result.success) {
@@ -304,7 +306,7 @@ export const cursorAdapter: AgentAdapter = {
         }
       }
 
-      onStream?.({ type: 'done' })
+      onStream?.({ type: 'done', plan: planMode })
 
       // Return parsed response, or error message if nothing was parsed
       if (fullResponse.trim()) {
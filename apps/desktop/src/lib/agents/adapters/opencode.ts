@@ -24,6 +24,7 @@ interface TauriStreamEvent {
   type: string
   data: string
   session_id: string
+  plan?: boolean
 }
 
 const config: AgentConfig = {
@@ -200,7 +201,7 @@ export const opencodeAdapter: AgentAdapter = {
   },
 
   async sendMessage(messages: AgentMessage[], options?: SendMessageOptions): Promise<string> {
-    const { systemPrompt, onStream, model, workingDirectory } = options || {}
+    const { systemPrompt, onStream, model, workingDirectory, extraArgs, planMode, workspaceId } = options || {}
     const prompt = buildPromptFromMessages(messages, systemPrompt)
     const sessionId = crypto.randomUUID()
 
@@ -252,7 +253,7 @@ export const opencodeAdapter: AgentAdapter = {
               onStream?.(streamEvent)
             }
           }
-          onStream?.({ type: 'done' })
+          onStream?.({ type: 'done', plan: payload.plan })
         }
       })
 
@@ -262,6 +263,9 @@ export const opencodeAdapter: AgentAdapter = {
         sessionId,
         model: model || null,
         workingDirectory: workingDirectory || null,
+        extraArgs: extraArgs ?? null,
+        planMode: planMode ?? false,
+        workspaceId: workspaceId ?? null,
       })
 
       // Clean up listener
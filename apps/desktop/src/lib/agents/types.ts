@@ -70,6 +70,8 @@ export interface StreamEvent {
   toolName?: string
   toolInput?: Record<string, unknown>
   toolResult?: string
+  /** On `done`: the run was in plan mode, so the output is a proposal, not applied changes */
+  plan?: boolean
 }
 
 /** Message format for agent communication */
@@ -78,12 +80,17 @@ export interface AgentMessage {
   content: string
 }
 
+/** Recognised causes of a failed agent run */
+export type AgentErrorKind = 'not_found' | 'auth' | 'rate_limit' | 'prompt_too_long' | 'network'
+
 /** Result from a Tauri command execution */
 export interface CommandResult {
   success: boolean
   stdout: string
   stderr: string
   code?: number
+  /** Set on failure when the cause is recognised, to pick a recovery action */
+  error_kind?: AgentErrorKind | null
 }
 
 /** Model information returned from an agent */
@@ -110,6 +117,15 @@ export interface SendMessageOptions {
   model?: string
   /** Working directory for the agent to run in */
   workingDirectory?: string
+  /**
+   * Extra CLI flags passed through to the agent. Output format and model flags are rejected,
+   * and so are permission, mode and agent flags when `planMode` is set.
+   */
+  extraArgs?: string[]
+  /** Propose changes without applying them, for agents with a plan/read-only mode */
+  planMode?: boolean
+  /** Workspace the run belongs to, so its transcript can be listed later */
+  workspaceId?: string
 }
 
 /**
@@ -13,6 +13,7 @@ interface CommandResult {
   stdout: string
   stderr: string
   code?: number
+  error_kind?: string | null
 }
 
 export interface ToolUseEvent {
@@ -119,6 +119,7 @@ describe('prdStorage', () => {
             content: JSON.stringify(prd, null, 2),
           },
         ],
+        overwrite: true,
       })
     })
   })
@@ -39,6 +39,7 @@ export async function copyPRDToWorkspace(prd: PRDDocument, workspacePath: string
   await invoke('write_project_files', {
     baseDir: workspacePath,
     files: [{ path: '.hatch/context/prd.json', content: JSON.stringify(prd, null, 2) }],
+    overwrite: true,
   })
 }
 
@@ -0,0 +1,6 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** Open an http(s) link in the system browser. Other schemes are rejected. */
+export async function openExternal(url: string): Promise<void> {
+  await invoke('open_external', { url })
+}
@@ -22,9 +22,14 @@ export interface WrittenFileManifest {
   size: number
 }
 
+/**
+ * Write code blocks under `baseDir`, overwriting existing files. With `atomic`,
+ * either every block is written or, on any failure, none are.
+ */
 export async function writeCodeBlocksToWorkspace(
   blocks: FileWriteBlock[],
-  baseDir: string
+  baseDir: string,
+  options: { atomic?: boolean } = {}
 ): Promise<WrittenFileManifest[]> {
   if (!baseDir || blocks.length === 0) {
     return []
@@ -38,6 +43,8 @@ export async function writeCodeBlocksToWorkspace(
   const results = await invoke<WriteProjectFileResult[]>('write_project_files', {
     files,
     baseDir,
+    overwrite: true,
+    ...(options.atomic ? { atomic: true } : {}),
   })
 
   const failed = results.filter((result) => !result.success)
@@ -9,19 +9,55 @@ export interface Repository {
   local_path: string
   default_branch: string
   is_private: boolean
+  /** Cloned with limited history; warn before history-dependent operations */
+  is_shallow?: boolean
 }
 
 export interface GitStatus {
   branch: string
   ahead: number
   behind: number
+  /** What ahead/behind are counted against, e.g. "upstream/main"; null if untracked */
+  upstream: string | null
   staged: string[]
   modified: string[]
   untracked: string[]
+  conflicted: string[]
+  /** Set while a merge, rebase, cherry-pick or revert is unfinished */
+  in_progress_operation: 'merge' | 'rebase' | 'cherry-pick' | 'revert' | null
+}
+
+export interface CommitSigningOptions {
+  /** Pass `-S` to sign with the configured key */
+  sign?: boolean
+  /** GPG key id or SSH key path; implies signing */
+  signingKey?: string
+}
+
+export interface BranchInfo {
+  /** Short name; remote branches include the remote, e.g. `origin/main` */
+  name: string
+  is_current: boolean
+  is_remote: boolean
+  upstream: string | null
+  ahead: number
+  behind: number
+}
+
+export interface CommitInfo {
+  hash: string
+  short_hash: string
+  author_name: string
+  author_email: string
+  /** Unix seconds */
+  timestamp: number
+  subject: string
 }
 
 export interface FileChange {
   path: string
+  /** Previous path when the file was renamed */
+  old_path?: string | null
   additions: number
   deletions: number
   status: 'modified' | 'added' | 'deleted' | 'renamed' | 'untracked'
@@ -32,6 +68,10 @@ export interface FileEntry {
   path: string
   is_directory: boolean
   children?: FileEntry[]
+  /** Size in bytes; null for directories or without `includeMetadata` */
+  size?: number | null
+  /** Last modified time (unix ms); null without `includeMetadata` */
+  modified?: number | null
 }
 
 export interface FileContent {
@@ -39,6 +79,15 @@ export interface FileContent {
   content: string
   language: string
   size: number
+  is_binary: boolean
+  total_lines: number
+}
+
+export interface BinaryFileContent {
+  path: string
+  content_base64: string
+  mime_type: string
+  size: number
 }
 
 export interface FileDiff {
@@ -48,6 +97,23 @@ export interface FileDiff {
   language: string
   is_new_file: boolean
   is_deleted: boolean
+  /** Contents are empty when binary; render sizes instead */
+  is_binary: boolean
+  old_size: number
+  new_size: number
+}
+
+export interface DiffLine {
+  kind: 'context' | 'add' | 'delete'
+  content: string
+}
+
+export interface DiffHunk {
+  old_start: number
+  old_lines: number
+  new_start: number
+  new_lines: number
+  lines: DiffLine[]
 }
 
 export interface WorkspaceResult {
@@ -62,6 +128,19 @@ interface WorktreeCreateInvokeResult {
   worktree_path?: string
 }
 
+/** What `worktreeCreate` would do, as reported by `worktreeCreateDryRun`. */
+export interface WorktreeCreatePlan {
+  branchName: string
+  worktreePath: string
+  startCommit: string | null
+  reusesExistingBranch: boolean
+  estimatedBytes: number | null
+  availableBytes: number | null
+  /** Reasons the create would fail; empty when it should succeed */
+  conflicts: string[]
+  warnings: string[]
+}
+
 export interface WorktreeLifecycleInfo {
   path: string
   branch: string
@@ -89,6 +168,34 @@ export interface PullRequestInfo {
   mergeable: boolean | null
   mergeable_state: string
   html_url: string
+  author: string | null
+  head_branch: string | null
+  base_branch: string | null
+}
+
+export interface CheckRun {
+  name: string
+  source: 'status' | 'check_run'
+  status: string
+  conclusion: string | null
+  url: string | null
+}
+
+export interface PrChecks {
+  state: 'success' | 'pending' | 'failure'
+  sha: string
+  checks: CheckRun[]
+}
+
+export interface PrDiff {
+  /** Raw unified diff */
+  diff: string
+  files: FileChange[]
+}
+
+export interface IssueInfo {
+  number: number
+  html_url: string
 }
 
 export interface MergeResult {
@@ -103,14 +210,20 @@ interface GitCoordinatorRequest {
   params: Record<string, unknown>
   priority: GitOperationPriority
   type: string
+  /** Overrides the coordinator's per-command default timeout */
+  timeoutSecs?: number
 }
 
 function commandPriority(command: string): GitOperationPriority {
   switch (command) {
     case 'git_commit':
+    case 'git_amend_commit':
     case 'git_push':
+    case 'git_ship':
+    case 'git_reset':
     case 'git_delete_workspace_branch':
     case 'git_merge_pr':
+    case 'git_abort':
       return 'critical'
     case 'git_diff':
     case 'git_diff_stats':
@@ -138,6 +251,38 @@ async function runCoordinatedGitCommand<T>(
   return invoke<T>('git_coordinator_enqueue', { request })
 }
 
+/** A coordinated git command for `runCoordinatedGitBatch`. */
+export interface CoordinatedGitCommand {
+  repoRoot: string
+  command: string
+  params: Record<string, unknown>
+  type: string
+}
+
+/** Outcome of one command in a batch; exactly one of `value` / `error` is set. */
+export interface CoordinatedGitBatchResult<T = unknown> {
+  value: T | null
+  error: string | null
+}
+
+/**
+ * Enqueue several git commands in one call. They are queued together, so no other
+ * caller's operations interleave, and results come back in the same order. Rejects
+ * without queueing anything if the batch doesn't fit in the repository's queue.
+ */
+export async function runCoordinatedGitBatch(
+  commands: CoordinatedGitCommand[]
+): Promise<CoordinatedGitBatchResult[]> {
+  const requests: GitCoordinatorRequest[] = commands.map(({ repoRoot, command, params, type }) => ({
+    repoRoot,
+    command,
+    params,
+    priority: commandPriority(command),
+    type,
+  }))
+  return invoke<CoordinatedGitBatchResult[]>('git_coordinator_enqueue_batch', { requests })
+}
+
 export async function getGitCoordinatorStatus(
   repoRoot: string
 ): Promise<GitCoordinatorQueueStatus> {
@@ -149,17 +294,88 @@ export async function cancelGitCoordinatorOperation(operationId: string): Promis
 }
 
 /**
- * Clone a repository from GitHub
+ * Cap how many operations may be pending for a repository before new non-critical
+ * ones are rejected. Pass null to restore the default (200); the limit must be at least 1.
+ */
+export async function setGitCoordinatorQueueLimit(
+  repoRoot: string,
+  maxPending: number | null
+): Promise<void> {
+  await invoke('git_coordinator_set_queue_limit', { request: { repoRoot, maxPending } })
+}
+
+/**
+ * Move a pending operation to another priority without losing its caller.
+ * Returns false if it's already running or no longer queued.
+ */
+export async function reprioritizeGitCoordinatorOperation(
+  operationId: string,
+  priority: GitOperationPriority
+): Promise<boolean> {
+  return invoke<boolean>('git_coordinator_reprioritize', { request: { operationId, priority } })
+}
+
+/**
+ * Cancel the running operation and every pending one for a repository
+ */
+export async function cancelGitCoordinatorRepo(repoRoot: string): Promise<number> {
+  return invoke<number>('git_coordinator_cancel_repo', { request: { repoRoot } })
+}
+
+/**
+ * Run an operation restored from a previous session. Only reads are replayed
+ * automatically; anything else waits in `awaitingConfirmation` until confirmed.
+ * Returns false if it is no longer awaiting confirmation.
  */
-export async function cloneRepo(repoUrl: string, repoName: string): Promise<Repository> {
+export async function confirmRestoredGitOperation(operationId: string): Promise<boolean> {
+  return invoke<boolean>('git_coordinator_confirm_restored', { request: { operationId } })
+}
+
+/**
+ * Drop an operation restored from a previous session without running it
+ */
+export async function discardRestoredGitOperation(operationId: string): Promise<boolean> {
+  return invoke<boolean>('git_coordinator_discard_restored', { request: { operationId } })
+}
+
+/**
+ * Drop operations restored from a previous session, including those awaiting
+ * confirmation, and delete the persisted queue
+ */
+export async function clearPersistedGitCoordinatorQueue(): Promise<number> {
+  return invoke<number>('git_coordinator_clear_persisted')
+}
+
+/**
+ * Clone a repository from GitHub. With `withSubmodules`, submodules are
+ * initialized too; listen for `clone-progress` events to show their progress.
+ */
+export async function cloneRepo(
+  repoUrl: string,
+  repoName: string,
+  options: {
+    sshCommand?: string
+    depth?: number
+    singleBranch?: boolean
+    withSubmodules?: boolean
+  } = {}
+): Promise<Repository> {
   return runCoordinatedGitCommand<Repository>(
     `clone:${repoName}`,
     'git_clone_repo',
-    { repoUrl, repoName },
+    { repoUrl, repoName, ...options },
     'clone'
   )
 }
 
+/**
+ * Initialize and update a repository's submodules, recursively. Progress is
+ * reported through `clone-progress` events.
+ */
+export async function updateSubmodules(repoPath: string): Promise<void> {
+  await runCoordinatedGitCommand<null>(repoPath, 'git_update_submodules', { repoPath }, 'submodules')
+}
+
 /**
  * Open an existing local repository
  */
@@ -172,24 +388,28 @@ export async function openLocalRepo(path: string): Promise<Repository> {
  */
 export async function createWorkspaceBranch(
   repoPath: string,
-  workspaceId: string
+  workspaceId: string,
+  baseRef?: string
 ): Promise<WorkspaceResult> {
   return runCoordinatedGitCommand<WorkspaceResult>(
     repoPath,
     'git_create_workspace_branch',
-    { repoPath, workspaceId },
+    { repoPath, workspaceId, baseRef },
     'worktree-create'
   )
 }
 
 export async function worktreeCreate(
   repoPath: string,
-  workspaceId: string
+  workspaceId: string,
+  options: { baseRef?: string; branchOverride?: string } = {}
 ): Promise<WorkspaceResult> {
   const result = await invoke<WorktreeCreateInvokeResult>('worktree_create', {
     request: {
       repoRoot: repoPath,
       workspaceId,
+      baseRef: options.baseRef,
+      branchOverride: options.branchOverride,
     },
   })
 
@@ -206,6 +426,25 @@ export async function worktreeCreate(
   }
 }
 
+/**
+ * Check whether `worktreeCreate` would succeed (branch conflicts, base ref, disk
+ * space) without fetching or creating anything.
+ */
+export async function worktreeCreateDryRun(
+  repoPath: string,
+  workspaceId: string,
+  options: { baseRef?: string; branchOverride?: string } = {}
+): Promise<WorktreeCreatePlan> {
+  return invoke<WorktreeCreatePlan>('worktree_create_dry_run', {
+    request: {
+      repoRoot: repoPath,
+      workspaceId,
+      baseRef: options.baseRef,
+      branchOverride: options.branchOverride,
+    },
+  })
+}
+
 export async function worktreeRemove(
   repoPath: string,
   worktreePath: string,
@@ -228,6 +467,25 @@ export async function worktreeRepair(repoPath: string): Promise<void> {
   })
 }
 
+export interface OrphanCleanupReport {
+  /** Paths of the worktrees that were removed */
+  cleaned: string[]
+  /** Steps that failed or were refused, such as deleting an unmerged branch */
+  errors: string[]
+}
+
+/**
+ * Remove orphaned worktrees and their merged workspace branches. Branches with
+ * unmerged commits are kept and listed in `errors`.
+ */
+export async function worktreeCleanOrphans(repoPath: string): Promise<OrphanCleanupReport> {
+  return invoke<OrphanCleanupReport>('worktree_clean_orphans', {
+    request: {
+      repoRoot: repoPath,
+    },
+  })
+}
+
 export async function worktreeList(repoPath: string): Promise<WorktreeLifecycleInfo[]> {
   return invoke<WorktreeLifecycleInfo[]>('worktree_list', {
     request: {
@@ -284,17 +542,266 @@ export async function getGitStatus(repoPath: string): Promise<GitStatus> {
 }
 
 /**
- * Commit all changes with the given message
+ * Commit with the given message. Stages all changes first unless `stagedOnly`
+ * is set, in which case only what's already staged is committed.
+ */
+export async function commitChanges(
+  repoPath: string,
+  message: string,
+  author?: { name: string; email: string },
+  signing?: CommitSigningOptions,
+  stagedOnly = false
+): Promise<string> {
+  return runCoordinatedGitCommand<string>(
+    repoPath,
+    'git_commit',
+    {
+      repoPath,
+      message,
+      authorName: author?.name,
+      authorEmail: author?.email,
+      sign: signing?.sign,
+      signingKey: signing?.signingKey,
+      stagedOnly,
+    },
+    'commit'
+  )
+}
+
+/**
+ * Stage the given files
+ */
+export async function stageFiles(repoPath: string, paths: string[]): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(repoPath, 'git_stage', { repoPath, paths }, 'stage')
+}
+
+/**
+ * Unstage the given files, keeping their working tree changes
+ */
+export async function unstageFiles(repoPath: string, paths: string[]): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(repoPath, 'git_unstage', { repoPath, paths }, 'unstage')
+}
+
+/**
+ * Discard local edits to the given files. Tracked files are restored from the
+ * index (staged changes are kept) and untracked files are deleted.
+ */
+export async function discardChanges(repoPath: string, paths: string[]): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(repoPath, 'git_discard', { repoPath, paths }, 'discard')
+}
+
+/**
+ * Set the commit identity in the repository's local git config
+ */
+export async function setIdentity(repoPath: string, name: string, email: string): Promise<void> {
+  await runCoordinatedGitCommand<null>(repoPath, 'git_set_identity', { repoPath, name, email }, 'config')
+}
+
+/**
+ * Amend the last commit with all current changes, keeping its message unless one is given
+ */
+export async function amendCommit(
+  repoPath: string,
+  message?: string,
+  signing?: CommitSigningOptions
+): Promise<string> {
+  return runCoordinatedGitCommand<string>(
+    repoPath,
+    'git_amend_commit',
+    { repoPath, message, sign: signing?.sign, signingKey: signing?.signingKey },
+    'commit'
+  )
+}
+
+/** Payload of the `push-progress` event emitted while a push runs. */
+export interface PushProgress {
+  repo_path: string
+  branch: string
+  /** Git's phase label, e.g. "Writing objects" */
+  phase: string
+  percent: number
+}
+
+/** Error prefix for a push rejected because the remote branch moved on. */
+export const PUSH_REJECTED_NON_FAST_FORWARD = 'Push rejected (non-fast-forward)'
+
+/** Error prefix for a force-with-lease push refused because someone else pushed. */
+export const PUSH_REJECTED_STALE_LEASE = 'Push rejected (stale lease)'
+
+/** Whether a push error means the branch needs a pull or rebase first. */
+export function isPushRejectedNonFastForward(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error)
+  return message.startsWith(PUSH_REJECTED_NON_FAST_FORWARD)
+}
+
+/**
+ * Push changes to remote. Listen for `push-progress` events to show progress.
+ * `forceWithLease` overwrites the remote branch after an amend or rebase, unless
+ * someone else has pushed to it since the last fetch.
+ */
+export async function pushChanges(
+  repoPath: string,
+  branch: string,
+  options: { forceWithLease?: boolean } = {}
+): Promise<void> {
+  await runCoordinatedGitCommand<null>(
+    repoPath,
+    'git_push',
+    { repoPath, branch, forceWithLease: options.forceWithLease },
+    'push'
+  )
+}
+
+/**
+ * Switch the worktree to another branch, creating it first when `create` is set
+ */
+export async function switchBranch(
+  repoPath: string,
+  branch: string,
+  create = false
+): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(
+    repoPath,
+    'git_switch_branch',
+    { repoPath, branch, create },
+    'switch-branch'
+  )
+}
+
+/**
+ * Move the current branch to `refOrCommit`. "soft" keeps changes staged,
+ * "mixed" keeps them unstaged, and "hard" discards them, so it's refused
+ * unless `confirmHard` is set.
+ */
+export async function resetBranch(
+  repoPath: string,
+  refOrCommit: string,
+  mode: 'soft' | 'mixed' | 'hard',
+  options: { confirmHard?: boolean } = {}
+): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(
+    repoPath,
+    'git_reset',
+    { repoPath, refOrCommit, mode, confirmHard: options.confirmHard },
+    'reset'
+  )
+}
+
+/** A configured git remote */
+export interface RemoteInfo {
+  name: string
+  fetch_url: string | null
+  push_url: string | null
+}
+
+/**
+ * List the repository's remotes
  */
-export async function commitChanges(repoPath: string, message: string): Promise<string> {
-  return runCoordinatedGitCommand<string>(repoPath, 'git_commit', { repoPath, message }, 'commit')
+export async function listRemotes(repoPath: string): Promise<RemoteInfo[]> {
+  return runCoordinatedGitCommand<RemoteInfo[]>(repoPath, 'git_list_remotes', { repoPath }, 'list-remotes')
 }
 
 /**
- * Push changes to remote
+ * Add a remote (e.g. `upstream` for a fork) and return the updated list
  */
-export async function pushChanges(repoPath: string, branch: string): Promise<void> {
-  await runCoordinatedGitCommand<null>(repoPath, 'git_push', { repoPath, branch }, 'push')
+export async function addRemote(repoPath: string, name: string, url: string): Promise<RemoteInfo[]> {
+  return runCoordinatedGitCommand<RemoteInfo[]>(
+    repoPath,
+    'git_add_remote',
+    { repoPath, name, url },
+    'add-remote'
+  )
+}
+
+/**
+ * Remove a remote and its remote-tracking branches, returning the updated list
+ */
+export async function removeRemote(repoPath: string, name: string): Promise<RemoteInfo[]> {
+  return runCoordinatedGitCommand<RemoteInfo[]>(
+    repoPath,
+    'git_remove_remote',
+    { repoPath, name },
+    'remove-remote'
+  )
+}
+
+/**
+ * Commit sha of the best common ancestor of two refs, the base of an
+ * `a...b` (PR-style) diff. Rejects if their histories never meet.
+ */
+export async function getMergeBase(repoPath: string, refA: string, refB: string): Promise<string> {
+  return runCoordinatedGitCommand<string>(repoPath, 'git_merge_base', { repoPath, refA, refB }, 'merge-base')
+}
+
+/** Outcome of `cherryPick` */
+export interface CherryPickResult {
+  /** False when the pick stopped on conflicts; resolve them or call `abortOperation` */
+  completed: boolean
+  commit_hash: string | null
+  conflicted: string[]
+  status: GitStatus
+}
+
+/**
+ * Apply a commit from another branch onto the current one
+ */
+export async function cherryPick(repoPath: string, commitSha: string): Promise<CherryPickResult> {
+  return runCoordinatedGitCommand<CherryPickResult>(
+    repoPath,
+    'git_cherry_pick',
+    { repoPath, commitSha },
+    'cherry-pick'
+  )
+}
+
+/** Outcome of `rebaseOnto` */
+export interface RebaseResult {
+  /** False when the rebase stopped on conflicts; resolve them or call `abortOperation` */
+  completed: boolean
+  conflicted: string[]
+  status: GitStatus
+}
+
+/**
+ * Fetch origin, then rebase the workspace branch onto `baseRef` (e.g. "origin/main")
+ */
+export async function rebaseOnto(repoPath: string, baseRef: string): Promise<RebaseResult> {
+  return runCoordinatedGitCommand<RebaseResult>(
+    repoPath,
+    'git_rebase_onto',
+    { repoPath, baseRef },
+    'rebase'
+  )
+}
+
+/**
+ * Abort an in-progress merge, rebase, cherry-pick or revert
+ */
+export async function abortOperation(repoPath: string): Promise<GitStatus> {
+  return runCoordinatedGitCommand<GitStatus>(repoPath, 'git_abort', { repoPath }, 'abort')
+}
+
+/**
+ * List local branches, plus remote-tracking branches when `includeRemote` is set
+ */
+export async function listBranches(repoPath: string, includeRemote = false): Promise<BranchInfo[]> {
+  return runCoordinatedGitCommand<BranchInfo[]>(
+    repoPath,
+    'git_list_branches',
+    { repoPath, includeRemote },
+    'list-branches'
+  )
+}
+
+/**
+ * Get recent commit history for the current branch or a given branch
+ */
+export async function getCommitLog(
+  repoPath: string,
+  limit?: number,
+  branch?: string
+): Promise<CommitInfo[]> {
+  return runCoordinatedGitCommand<CommitInfo[]>(repoPath, 'git_log', { repoPath, limit, branch }, 'log')
 }
 
 /**
@@ -315,6 +822,61 @@ export async function createPR(
   )
 }
 
+/** How far `shipChanges` got. Steps after `failed_step` weren't attempted. */
+export interface ShipResult {
+  committed: boolean
+  /** Short hash of the new commit, or null if there was nothing to commit */
+  commit_hash: string | null
+  pushed: boolean
+  pr_url: string | null
+  failed_step: 'commit' | 'push' | 'pr' | null
+  error: string | null
+}
+
+export interface ShipRequest {
+  repoPath: string
+  repoFullName: string
+  branch: string
+  message: string
+  baseBranch: string
+  title: string
+  body: string
+}
+
+/**
+ * Commit all changes, push the branch and open a pull request as one
+ * coordinated operation, stopping at the first failing step. Failures are
+ * reported in the result, not thrown, so callers can see what already happened.
+ */
+export async function shipChanges(request: ShipRequest): Promise<ShipResult> {
+  return runCoordinatedGitCommand<ShipResult>(
+    request.repoPath,
+    'git_ship',
+    { ...request },
+    'ship'
+  )
+}
+
+/**
+ * Create a GitLab merge request for the local clone at `repoPath` (token is read
+ * from the `gitlab_access_token` keychain entry)
+ */
+export async function createMergeRequest(
+  repoPath: string,
+  project: string,
+  sourceBranch: string,
+  targetBranch: string,
+  title: string,
+  description: string
+): Promise<string> {
+  return runCoordinatedGitCommand<string>(
+    repoPath,
+    'gitlab_create_mr',
+    { project, sourceBranch, targetBranch, title, description },
+    'create-mr'
+  )
+}
+
 /**
  * Create a new GitHub repository
  */
@@ -327,11 +889,20 @@ export async function createGitHubRepo(name: string, isPrivate: boolean): Promis
   )
 }
 
+/**
+ * Refs to compare. With both set, diffs `baseRef...targetRef` (from their merge
+ * base); with only `baseRef`, diffs the working tree against it.
+ */
+export interface DiffRefs {
+  baseRef?: string
+  targetRef?: string
+}
+
 /**
  * Get the diff for a repository
  */
-export async function getDiff(repoPath: string): Promise<string> {
-  return runCoordinatedGitCommand<string>(repoPath, 'git_diff', { repoPath }, 'diff')
+export async function getDiff(repoPath: string, refs: DiffRefs = {}): Promise<string> {
+  return runCoordinatedGitCommand<string>(repoPath, 'git_diff', { repoPath, ...refs }, 'diff')
 }
 
 /**
@@ -369,35 +940,127 @@ export async function getDiffStats(repoPath: string): Promise<FileChange[]> {
 }
 
 /**
- * List all files in a directory recursively
+ * List all files in a directory recursively. Pass `followSymlinks: false` to list
+ * symlinked directories without descending into them, and `includeMetadata` to
+ * get sizes and modified times (one extra stat per entry). `ignoreNames` skips
+ * entries by name (e.g. ".venv", "dist"); `ignoreGlobs` skips by pattern, matched
+ * against the relative path when it contains a `/` and the name otherwise.
  */
 export async function listDirectoryFiles(
   dirPath: string,
   maxDepth?: number,
-  showHidden?: boolean
+  showHidden?: boolean,
+  respectGitignore?: boolean,
+  followSymlinks?: boolean,
+  includeMetadata?: boolean,
+  ignoreNames?: string[],
+  ignoreGlobs?: string[]
 ): Promise<FileEntry[]> {
-  return invoke<FileEntry[]>('list_directory_files', { dirPath, maxDepth, showHidden })
+  return invoke<FileEntry[]>('list_directory_files', {
+    dirPath,
+    maxDepth,
+    showHidden,
+    respectGitignore,
+    followSymlinks,
+    includeMetadata,
+    ignoreNames,
+    ignoreGlobs,
+  })
+}
+
+/** One page of a directory's immediate children */
+export interface DirectoryChildren {
+  entries: FileEntry[]
+  /** Number of children in the whole directory, not just this page */
+  total_count: number
+}
+
+/**
+ * Read a directory's immediate children, directories first then alphabetical.
+ * Pass `offset`/`limit` to page through very large directories.
+ */
+export async function readDirectoryChildren(
+  path: string,
+  offset?: number,
+  limit?: number
+): Promise<DirectoryChildren> {
+  return invoke<DirectoryChildren>('read_directory_children', { path, offset, limit })
+}
+
+/**
+ * Fuzzy-find files in a repository by path, best matches first. Skips hidden
+ * files, node_modules and target like the file tree.
+ */
+export async function searchFiles(repoPath: string, query: string, limit?: number): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>('search_files', { repoPath, query, limit })
+}
+
+/** A line matching a content search */
+export interface SearchHit {
+  path: string
+  line_number: number
+  line_text: string
+}
+
+/**
+ * Search file contents for `query`, literally or as a regex. Inside a git repo
+ * gitignored files are skipped. Returns at most `maxResults` hits (default 200).
+ */
+export async function searchInFiles(
+  repoPath: string,
+  query: string,
+  options: { isRegex?: boolean; maxResults?: number } = {}
+): Promise<SearchHit[]> {
+  return invoke<SearchHit[]>('search_in_files', {
+    repoPath,
+    query,
+    isRegex: options.isRegex,
+    maxResults: options.maxResults,
+  })
 }
 
 /**
  * Read the contents of a file
  */
-export async function readFile(filePath: string): Promise<FileContent> {
-  return invoke<FileContent>('read_file', { filePath })
+export async function readFile(
+  filePath: string,
+  startLine?: number,
+  endLine?: number
+): Promise<FileContent> {
+  return invoke<FileContent>('read_file', { filePath, startLine, endLine })
+}
+
+/**
+ * Read a binary file (e.g. an image) as base64 with its MIME type
+ */
+export async function readFileBinary(filePath: string): Promise<BinaryFileContent> {
+  return invoke<BinaryFileContent>('read_file_binary', { filePath })
 }
 
 /**
  * Get diff for a specific file (old content vs new content)
  */
-export async function getFileDiff(repoPath: string, filePath: string): Promise<FileDiff> {
+export async function getFileDiff(repoPath: string, filePath: string, refs: DiffRefs = {}): Promise<FileDiff> {
   return runCoordinatedGitCommand<FileDiff>(
     repoPath,
     'git_file_diff',
-    { repoPath, filePath },
+    { repoPath, filePath, ...refs },
     'file-diff'
   )
 }
 
+/**
+ * Get a file's changes against HEAD as unified diff hunks
+ */
+export async function getFileHunks(repoPath: string, filePath: string): Promise<DiffHunk[]> {
+  return runCoordinatedGitCommand<DiffHunk[]>(
+    repoPath,
+    'git_file_hunks',
+    { repoPath, filePath },
+    'file-hunks'
+  )
+}
+
 /**
  * Get pull request details from GitHub
  */
@@ -413,6 +1076,85 @@ export async function getPullRequest(
   )
 }
 
+export interface PullRequestList {
+  prs: PullRequestInfo[]
+  /** More PRs exist beyond the first 1000 that were listed */
+  truncated: boolean
+}
+
+/**
+ * List pull requests for a repository (state defaults to open)
+ */
+export async function listPullRequests(
+  repoFullName: string,
+  state?: 'open' | 'closed' | 'all'
+): Promise<PullRequestList> {
+  return runCoordinatedGitCommand<PullRequestList>(
+    `github:${repoFullName}`,
+    'github_list_prs',
+    { repoFullName, state },
+    'list-prs'
+  )
+}
+
+/**
+ * Find the open PR for a branch, or null if there isn't one. Pass
+ * `owner:branch` as `headBranch` for a PR opened from a fork.
+ */
+export async function findPullRequestForBranch(
+  repoFullName: string,
+  headBranch: string
+): Promise<PullRequestInfo | null> {
+  return runCoordinatedGitCommand<PullRequestInfo | null>(
+    `github:${repoFullName}`,
+    'github_find_pr_for_branch',
+    { repoFullName, headBranch },
+    'find-pr'
+  )
+}
+
+/**
+ * Get the aggregate CI state for a PR's head commit (use to gate merging)
+ */
+export async function getPullRequestChecks(repoFullName: string, prNumber: number): Promise<PrChecks> {
+  return runCoordinatedGitCommand<PrChecks>(
+    `github:${repoFullName}`,
+    'github_get_pr_checks',
+    { repoFullName, prNumber },
+    'pr-checks'
+  )
+}
+
+/**
+ * Get a PR's unified diff and per-file stats from GitHub
+ */
+export async function getPullRequestDiff(repoFullName: string, prNumber: number): Promise<PrDiff> {
+  return runCoordinatedGitCommand<PrDiff>(
+    `github:${repoFullName}`,
+    'github_get_pr_diff',
+    { repoFullName, prNumber },
+    'pr-diff'
+  )
+}
+
+/**
+ * Create a GitHub issue
+ */
+export async function createIssue(
+  repoFullName: string,
+  title: string,
+  body: string,
+  labels: string[] = [],
+  assignees?: string[]
+): Promise<IssueInfo> {
+  return runCoordinatedGitCommand<IssueInfo>(
+    `github:${repoFullName}`,
+    'github_create_issue',
+    { repoFullName, title, body, labels, assignees },
+    'create-issue'
+  )
+}
+
 /**
  * Merge a pull request
  */
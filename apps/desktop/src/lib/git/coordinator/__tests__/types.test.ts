@@ -22,6 +22,7 @@ describe('GitCoordinator Types', () => {
           runningOperation: null,
           completedCount: 0,
           failedCount: 0,
+          awaitingConfirmation: [],
         }
         return status
       },
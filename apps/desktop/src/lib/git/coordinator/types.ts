@@ -81,6 +81,9 @@ export interface GitCoordinatorQueueStatus {
 
   /** Number of failed operations */
   failedCount: number
+
+  /** Operations restored from a previous session that must be re-approved before they run */
+  awaitingConfirmation: GitOperation[]
 }
 
 /** Main git coordinator interface */
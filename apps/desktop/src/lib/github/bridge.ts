@@ -12,6 +12,10 @@ export interface GitHubAuthState {
   access_token?: string
   user?: GitHubUser
   is_authenticated: boolean
+  /** OAuth scopes granted to the token; empty when GitHub doesn't report them */
+  scopes: string[]
+  /** Set at sign-in when the token lacks the `repo` scope */
+  scope_warning?: string
 }
 
 /**
@@ -29,6 +33,13 @@ export async function login(): Promise<GitHubAuthState> {
   return invoke<GitHubAuthState>('github_login')
 }
 
+/**
+ * Log in with a personal access token (requires the `repo` scope)
+ */
+export async function loginWithToken(token: string): Promise<GitHubAuthState> {
+  return invoke<GitHubAuthState>('github_login_with_token', { token })
+}
+
 /**
  * Get the current auth state
  */
@@ -44,10 +55,10 @@ export async function signOut(): Promise<void> {
 }
 
 /**
- * Validate the stored token by checking against GitHub API
+ * Validate the stored token against the GitHub API, returning its granted scopes
  */
-export async function validateToken(): Promise<GitHubUser> {
-  return invoke<GitHubUser>('github_validate_token')
+export async function validateToken(): Promise<GitHubAuthState> {
+  return invoke<GitHubAuthState>('github_validate_token')
 }
 
 
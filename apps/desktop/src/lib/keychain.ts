@@ -6,24 +6,40 @@ export type KeychainKey = 'anthropic_api_key' | 'cf_account_id' | 'cf_api_token'
 /** All keychain key names, for iteration. */
 export const KEYCHAIN_KEYS: KeychainKey[] = ['anthropic_api_key', 'cf_account_id', 'cf_api_token', 'herenow_api_token', 'railway_api_token']
 
-/** Store a secret in the OS keychain. */
-export async function keychainSet(key: KeychainKey, value: string): Promise<void> {
-  await invoke('keychain_set', { key, value })
+/**
+ * Store a secret in the OS keychain. Pass a namespace (e.g. `account:<login>`)
+ * to keep per-account secrets isolated.
+ */
+export async function keychainSet(key: KeychainKey, value: string, namespace?: string): Promise<void> {
+  await invoke('keychain_set', { key, value, namespace })
 }
 
 /** Retrieve a secret from the OS keychain. Returns null if not set. */
-export async function keychainGet(key: KeychainKey): Promise<string | null> {
-  return invoke<string | null>('keychain_get', { key })
+export async function keychainGet(key: KeychainKey, namespace?: string): Promise<string | null> {
+  return invoke<string | null>('keychain_get', { key, namespace })
 }
 
 /** Delete a secret from the OS keychain. */
-export async function keychainDelete(key: KeychainKey): Promise<void> {
-  await invoke('keychain_delete', { key })
+export async function keychainDelete(key: KeychainKey, namespace?: string): Promise<void> {
+  await invoke('keychain_delete', { key, namespace })
+}
+
+/** Delete every secret stored under a namespace. */
+export async function keychainDeleteNamespace(namespace: string): Promise<void> {
+  await invoke('keychain_delete_namespace', { namespace })
 }
 
 /** Check if a key exists in the OS keychain with a non-empty value. */
-export async function keychainHas(key: KeychainKey): Promise<boolean> {
-  return invoke<boolean>('keychain_has', { key })
+export async function keychainHas(key: KeychainKey, namespace?: string): Promise<boolean> {
+  return invoke<boolean>('keychain_has', { key, namespace })
+}
+
+/**
+ * List the keys stored in the OS keychain (names only, never values). With a
+ * namespace, lists that namespace's keys without the prefix.
+ */
+export async function keychainList(namespace?: string): Promise<string[]> {
+  return invoke<string[]>('keychain_list', { namespace })
 }
 
 /** Fetch all service credentials from the keychain. */
@@ -0,0 +1,30 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** An MCP server Claude Code can launch (stdio) or connect to (http/sse). */
+export interface McpServerConfig {
+  type?: 'stdio' | 'http' | 'sse'
+  command?: string
+  args?: string[]
+  env?: Record<string, string>
+  url?: string
+  headers?: Record<string, string>
+}
+
+/** MCP config in the `--mcp-config` file format. */
+export interface McpConfig {
+  mcpServers: Record<string, McpServerConfig>
+}
+
+/** Read the saved MCP config. Returns null if none has been saved. */
+export async function mcpGetConfig(): Promise<McpConfig | null> {
+  return invoke<McpConfig | null>('mcp_get_config')
+}
+
+/**
+ * Save the MCP config applied to every Claude Code run. Accepts raw JSON text so
+ * hand-edited configs get the backend's parse and shape errors.
+ */
+export async function mcpSetConfig(config: McpConfig | string): Promise<void> {
+  const json = typeof config === 'string' ? config : JSON.stringify(config)
+  await invoke('mcp_set_config', { json })
+}
@@ -83,6 +83,7 @@ export async function writeProjectMemory(workspacePath: string, content: string)
   const results = await invoke<WriteProjectFileResult[]>('write_project_files', {
     files,
     baseDir: workspacePath,
+    overwrite: true,
   })
 
   const failed = results.filter((result) => !result.success)
@@ -0,0 +1,56 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+
+/** Payload of the `repo-changed` event. */
+export interface RepoChangedEvent {
+  session_id: string
+  repo_path: string
+  /** Changed paths relative to the repo, sorted. */
+  paths: string[]
+}
+
+/** Payload of the `repo-watch-error` event. Changes may have been missed. */
+export interface RepoWatchErrorEvent {
+  session_id: string
+  repo_path: string
+  error: string
+}
+
+/**
+ * Start watching `repoPath` for file changes. Changes are debounced and
+ * reported as `repo-changed` events tagged with `sessionId`; `.git`,
+ * `node_modules` and `target` are ignored. Watching again with the same
+ * session id replaces the previous watcher.
+ */
+export async function watchRepo(repoPath: string, sessionId: string): Promise<void> {
+  await invoke('watch_repo', { repoPath, sessionId })
+}
+
+/** Stop the watcher started for `sessionId`. */
+export async function unwatchRepo(sessionId: string): Promise<void> {
+  await invoke('unwatch_repo', { sessionId })
+}
+
+/** Listen for change events from the watcher started for `sessionId`. */
+export async function onRepoChanged(
+  sessionId: string,
+  handler: (event: RepoChangedEvent) => void
+): Promise<UnlistenFn> {
+  return listen<RepoChangedEvent>('repo-changed', (event) => {
+    if (event.payload.session_id === sessionId) {
+      handler(event.payload)
+    }
+  })
+}
+
+/** Listen for failures of the watcher started for `sessionId`. */
+export async function onRepoWatchError(
+  sessionId: string,
+  handler: (event: RepoWatchErrorEvent) => void
+): Promise<UnlistenFn> {
+  return listen<RepoWatchErrorEvent>('repo-watch-error', (event) => {
+    if (event.payload.session_id === sessionId) {
+      handler(event.payload)
+    }
+  })
+}
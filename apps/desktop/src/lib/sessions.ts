@@ -0,0 +1,40 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** A recorded agent stream event. */
+export interface TranscriptEvent {
+  type: string
+  data: string
+  timestamp: number
+}
+
+/** A stored agent session, as listed for a workspace. */
+export interface SessionSummary {
+  session_id: string
+  agent: string
+  workspace_id: string | null
+  started_at: number
+}
+
+/** Read back the stream events recorded for a session, oldest first. */
+export async function getSessionTranscript(sessionId: string): Promise<TranscriptEvent[]> {
+  return invoke<TranscriptEvent[]>('get_session_transcript', { sessionId })
+}
+
+/** List the sessions recorded for a workspace, newest first. */
+export async function listSessions(workspaceId: string): Promise<SessionSummary[]> {
+  return invoke<SessionSummary[]>('list_sessions', { workspaceId })
+}
+
+/**
+ * Read the last `lines` lines an agent session printed (stdout and stderr
+ * interleaved). Kept in memory after the run ends, until the session is cleared
+ * or 50 newer runs have finished.
+ */
+export async function getAgentTail(sessionId: string, lines?: number): Promise<string[]> {
+  return invoke<string[]>('get_agent_tail', { sessionId, lines })
+}
+
+/** Delete a session's transcript and its retained output tail. */
+export async function clearSession(sessionId: string): Promise<void> {
+  await invoke('clear_session', { sessionId })
+}
@@ -18,6 +18,13 @@ interface SkillInstallResult {
   path: string | null
 }
 
+export interface InstalledSkill {
+  name: string
+  version: string | null
+  description: string | null
+  path: string
+}
+
 // Check if we're running in Tauri
 export function isTauri(): boolean {
   return typeof window !== 'undefined' && '__TAURI__' in window
@@ -280,6 +287,47 @@ export async function installSkillViaTauri(
   })
 }
 
+/**
+ * Download a .zip or .tar.gz skill archive and install it using Tauri backend
+ */
+export async function installSkillFromUrlViaTauri(
+  skillName: string,
+  url: string,
+  isGlobal: boolean,
+  workingDirectory?: string
+): Promise<SkillInstallResult> {
+  const invoke = await getTauriInvoke()
+
+  return invoke<SkillInstallResult>('install_skill_from_url', {
+    skillName,
+    url,
+    isGlobal,
+    workingDirectory,
+  })
+}
+
+/**
+ * Update an installed skill using Tauri backend. Files are only rewritten when
+ * the incoming version is newer, unless `force` is set.
+ */
+export async function updateSkillViaTauri(
+  skillName: string,
+  files: SkillFile[],
+  isGlobal: boolean,
+  workingDirectory?: string,
+  force?: boolean
+): Promise<SkillInstallResult> {
+  const invoke = await getTauriInvoke()
+
+  return invoke<SkillInstallResult>('update_skill', {
+    skillName,
+    files,
+    isGlobal,
+    workingDirectory,
+    force,
+  })
+}
+
 /**
  * Uninstall a skill using Tauri backend
  */
@@ -303,10 +351,10 @@ export async function uninstallSkillViaTauri(
 export async function listInstalledSkillsViaTauri(
   isGlobal: boolean,
   workingDirectory?: string
-): Promise<string[]> {
+): Promise<InstalledSkill[]> {
   const invoke = await getTauriInvoke()
 
-  return invoke<string[]>('list_installed_skills', {
+  return invoke<InstalledSkill[]>('list_installed_skills', {
     isGlobal,
     workingDirectory,
   })
@@ -346,6 +394,52 @@ export async function getSkillInstallPathViaTauri(
   })
 }
 
+export interface ShellStreamEvent {
+  type: 'line' | 'stderr' | 'done'
+  data: string
+  session_id: string
+}
+
+/**
+ * Run a shell command via Tauri, reporting each output line as it arrives.
+ * The final `done` event carries the exit code.
+ */
+export async function runShellCommandStreaming(
+  command: string,
+  onEvent: (event: ShellStreamEvent) => void,
+  workingDirectory?: string,
+  options: { sessionId?: string; timeoutSecs?: number } = {}
+): Promise<SkillInstallResult> {
+  const invoke = await getTauriInvoke()
+  const { listen } = await import('@tauri-apps/api/event')
+  const sessionId = options.sessionId ?? `shell-${Date.now()}-${Math.random().toString(36).slice(2, 8)}`
+
+  const unlisten = await listen<ShellStreamEvent>('shell-stream', (event) => {
+    if (event.payload.session_id === sessionId) {
+      onEvent(event.payload)
+    }
+  })
+
+  try {
+    return await invoke<SkillInstallResult>('run_shell_command_streaming', {
+      command,
+      workingDirectory,
+      sessionId,
+      timeoutSecs: options.timeoutSecs,
+    })
+  } finally {
+    unlisten()
+  }
+}
+
+/**
+ * Stop a running shell command (and everything it started) by session id
+ */
+export async function cancelShellCommand(sessionId: string): Promise<void> {
+  const invoke = await getTauriInvoke()
+  await invoke('cancel_shell_command', { sessionId })
+}
+
 /**
  * Check if a skill is from aitmpl.com (claude-code-templates)
  */
@@ -458,8 +552,8 @@ export async function installSkill(
  * Get installed skills (both local and global)
  */
 export async function getInstalledSkills(workingDirectory?: string): Promise<{
-  local: string[]
-  global: string[]
+  local: InstalledSkill[]
+  global: InstalledSkill[]
 }> {
   if (!isTauri()) {
     return { local: [], global: [] }
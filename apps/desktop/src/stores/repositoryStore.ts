@@ -217,7 +217,7 @@ export const useRepositoryStore = create<RepositoryState>()(
           set({
             githubAuth: auth,
             isAuthenticating: false,
-            authError: null,
+            authError: auth.scope_warning ?? null,
             pendingAuthRetry: null,
           })
 
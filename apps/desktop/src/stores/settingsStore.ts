@@ -27,14 +27,19 @@ export type BranchNamePrefix = 'github-username' | 'custom' | 'none'
 /** Model options for agents - can be "default" or any model ID string */
 export type AgentModel = string
 
+/** Local agents that let the user pick a model */
+export type ModelSelectableAgent = 'claude-code' | 'opencode' | 'cursor'
+
 /** Agent model configuration */
 export interface AgentModelConfig {
+  'claude-code': string
   opencode: string
   cursor: string
 }
 
 /** Available models for each agent */
 export interface AvailableAgentModels {
+  'claude-code': ModelInfo[]
   opencode: ModelInfo[]
   cursor: ModelInfo[]
 }
@@ -136,14 +141,17 @@ interface SettingsState {
   setArchiveOnMerge: (enabled: boolean) => void
 
   // Agent model actions
-  setAgentModel: (agent: 'opencode' | 'cursor', model: string) => void
+  setAgentModel: (agent: ModelSelectableAgent, model: string) => void
 
   /** Fetch available models from an agent */
-  fetchAgentModels: (agent: 'opencode' | 'cursor') => Promise<ModelInfo[]>
+  fetchAgentModels: (agent: ModelSelectableAgent) => Promise<ModelInfo[]>
 
   /** Check status for a local CLI agent */
   checkAgentStatus: (agentId: LocalAgentId) => Promise<AgentStatus>
 
+  /** Check status for every local CLI agent in one concurrent backend call */
+  checkAllAgentStatuses: () => Promise<void>
+
   /** Legacy: Get Claude Code status (for backwards compatibility) */
   get claudeCodeStatus(): AgentStatus | null
 
@@ -203,12 +211,14 @@ export const useSettingsStore = create<SettingsState>()(
 
       // Agent model defaults
       agentModels: {
+        'claude-code': 'default',
         opencode: 'default',
         cursor: 'default',
       },
 
       // Available models (fetched dynamically)
       availableModels: {
+        'claude-code': [],
         opencode: [],
         cursor: [],
       },
@@ -318,6 +328,21 @@ export const useSettingsStore = create<SettingsState>()(
         }
       },
 
+      checkAllAgentStatuses: async () => {
+        set({ isCheckingAgent: true })
+        try {
+          const statuses = await invoke<Partial<Record<LocalAgentId, AgentStatus>>>('check_all_agents')
+          for (const agentId of LOCAL_AGENT_IDS) {
+            get().setAgentStatus(agentId, statuses[agentId] ?? null)
+          }
+        } catch (error) {
+          const message = error instanceof Error ? error.message : 'Failed to check agent status'
+          for (const agentId of LOCAL_AGENT_IDS) {
+            get().setAgentStatus(agentId, { installed: false, authenticated: false, error: message })
+          }
+        }
+      },
+
       // Legacy getter for backwards compatibility
       get claudeCodeStatus() {
         return get().agentStatuses['claude-code']